@@ -0,0 +1,84 @@
+use crate::convolution::karatsuba;
+use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// （可換な）加法群。```+```, ```-```と零元を持ち、値どうしの比較ができることを要求。
+///
+/// ```Polynomial```のような```Copy```でない型も扱いたいので、```Copy```ではなく```Clone```を要求する。
+pub trait AdditiveGroup:
+    Clone
+    + Eq
+    + Zero
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Neg<Output = Self>
+{
+}
+
+/// （単位元を持つ）環。可換とは限らない。
+pub trait Ring: AdditiveGroup + Identity + Mul<Output = Self> + MulAssign {
+    /// 係数列どうしの畳み込み。多項式の掛け算で使う。
+    /// デフォルトはカラツバ法で、```ModInt```のようにNTTが使える型ではより速い方法で上書きする。
+    fn convolution(a: &[Self], b: &[Self]) -> Vec<Self> {
+        karatsuba(a, b)
+    }
+}
+
+/// 可換環。掛け算が可換であることは実装する側が保証する。
+pub trait CommutativeRing: Ring {}
+
+/// 整域。零因子を持たないことは実装する側が保証する。
+pub trait IntegralDomain: CommutativeRing {}
+
+/// ユークリッド整域。
+pub trait EuclideanDomain: IntegralDomain {
+    /// ユークリッド関数。0に対しては0を、それ以外に対しては正の値を返すことにする。
+    fn norm(&self) -> u64;
+
+    /// 余り付きの割り算。```(q, r)```で、```a = q * b + r```かつ```r```のノルムが```b```のノルムより小さいもの。
+    /// ```b```が0のときはpanicする。
+    fn div_rem(a: &Self, b: &Self) -> (Self, Self);
+
+    /// 単元倍の違いを揃えるための単元。```a * a.normalizing_unit()```が代表元
+    /// （整数なら0以上、体係数の多項式ならmonic）になるようなものを返す。デフォルトは1（揃えない）。
+    fn normalizing_unit(&self) -> Self {
+        Self::identity()
+    }
+
+    /// ユークリッドの互除法による最大公約数。単元倍の違いは気にしない。
+    fn gcd(a: &Self, b: &Self) -> Self {
+        let mut x = a.clone();
+        let mut y = b.clone();
+        while y != Self::zero() {
+            let (_, r) = Self::div_rem(&x, &y);
+            x = y;
+            y = r;
+        }
+        x
+    }
+
+    /// 拡張ユークリッドの互除法。```(g, s, t)```で、```g```は最大公約数、```s * a + t * b = g```となるもの。
+    fn extended_gcd(a: &Self, b: &Self) -> (Self, Self, Self) {
+        let (mut r0, mut r1) = (a.clone(), b.clone());
+        let (mut s0, mut s1) = (Self::identity(), Self::zero());
+        let (mut t0, mut t1) = (Self::zero(), Self::identity());
+        while r1 != Self::zero() {
+            let (q, r) = Self::div_rem(&r0, &r1);
+            r0 = r1;
+            r1 = r;
+            let s = s0 - q.clone() * s1.clone();
+            s0 = s1;
+            s1 = s;
+            let t = t0 - q * t1.clone();
+            t0 = t1;
+            t1 = t;
+        }
+        (r0, s0, t0)
+    }
+}
+
+/// 体。0以外の元が逆元を持つ（```Inverse```が```Some```を返す）ことは実装する側が保証する。
+pub trait Field: IntegralDomain + Inverse {}
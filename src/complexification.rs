@@ -1,7 +1,16 @@
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, Field, IntegralDomain, Ring};
 use crate::characteristic::Characteristic;
+use crate::format::{Notation, PolynomialFormat};
 use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::parse::{parse_coefficient, split_terms, strip_parentheses, strip_variable, ParseError};
+use crate::polynomial::Polynomial;
+use crate::real_number::RealNumber;
+use crate::scalar::Scalar;
 use std::fmt;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// R\[x\]/(x^2 + 1) の元。
 /// ここでRは型```T```の対象のなす環。
@@ -14,10 +23,17 @@ pub struct Complex<T> {
 impl<T> Complex<T> {
     /// コンストラクタ。1つめの引数が定数項。2つめが1次の項。3つめと4つめが割るイデアルの生成元の1次の係数と定数項。
     pub fn new(real: T, imaginary: T) -> Self {
-        Self {
-            real: real,
-            imaginary: imaginary,
-        }
+        Self { real, imaginary }
+    }
+
+    /// 実部
+    pub fn real(&self) -> &T {
+        &self.real
+    }
+
+    /// 虚部
+    pub fn imaginary(&self) -> &T {
+        &self.imaginary
     }
 }
 
@@ -29,6 +45,40 @@ impl<T: fmt::Display> fmt::Display for Complex<T> {
     }
 }
 
+/// ```3 + (-5)i```（```Display```の形式）や```3 - 5i```、```2i```、```-i```、```7```を読む。
+/// 全体が括弧で囲まれていてもよい（多項式の係数として```(3 + 4i)x```のように書かれたときのため）。
+/// 実部や虚部の表示に括弧の外の```+```, ```-```が含まれる型には使えない。
+impl<T> FromStr for Complex<T>
+where
+    T: Copy + Zero + Identity + Add<Output = T> + Neg<Output = T> + FromStr<Err = ParseError>,
+{
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut real = T::zero();
+        let mut imaginary = T::zero();
+        let (s, o) = strip_parentheses(s, 0);
+        for (negative, t, o) in split_terms(s, o)? {
+            let (part, value) = match strip_variable(t, 'i') {
+                Some(c) => (&mut imaginary, parse_coefficient::<T>(c, o)?),
+                None => (&mut real, T::from_str(t).map_err(|e| e.shift(o))?),
+            };
+            *part = *part + if negative { -value } else { value };
+        }
+        Ok(Complex::new(real, imaginary))
+    }
+}
+
+/// ```3 - 4i```のように書く。実部や虚部は```T```の```to_latex()```で書く。
+impl<T: Ring + ToLatex> ToLatex for Complex<T> {
+    fn to_latex(&self) -> String {
+        let f = Polynomial::new(&[self.real.clone(), self.imaginary.clone()]);
+        PolynomialFormat::new()
+            .variable("i")
+            .notation(Notation::Latex)
+            .polynomial_with(&f, T::to_latex)
+    }
+}
+
 /// 足し算の実装。
 /// これら演算は、bやcが異なっている場合（すなわち異なる環の元どうしでの演算を試みた場合）、
 /// R\[x\] / (x^2) の元 0 を返すことにする。
@@ -122,3 +172,45 @@ impl<T: Characteristic> Characteristic for Complex<T> {
         T::characteristic()
     }
 }
+
+/// 逆元の実装。(a + bi)^(-1) = (a - bi) / (a^2 + b^2) で計算し、a^2 + b^2 が可逆でなければ```None```を返す。
+impl<T: Field + Copy> Inverse for Complex<T> {
+    fn inverse(self) -> Option<Complex<T>> {
+        let norm = self.real * self.real + self.imaginary * self.imaginary;
+        norm.inverse().map(|n| Self {
+            real: self.real * n,
+            imaginary: -self.imaginary * n,
+        })
+    }
+}
+
+/// 複素数の数値計算用。絶対値は```f64::hypot```で求める。
+impl Scalar for Complex<RealNumber> {
+    fn from_f64(x: f64) -> Self {
+        Complex::new(RealNumber::new(x), RealNumber::new(0f64))
+    }
+
+    fn real_part(&self) -> f64 {
+        self.real.to_f64()
+    }
+
+    fn abs(&self) -> f64 {
+        self.real.to_f64().hypot(self.imaginary.to_f64())
+    }
+
+    fn conjugate(&self) -> Self {
+        Complex::new(self.real, -self.imaginary)
+    }
+}
+
+impl<T: CommutativeRing + Copy> AdditiveGroup for Complex<T> {}
+
+impl<T: CommutativeRing + Copy> Ring for Complex<T> {}
+
+impl<T: CommutativeRing + Copy> CommutativeRing for Complex<T> {}
+
+/// x^2 + 1 が係数環上で既約であることは使う側が保証する。
+impl<T: IntegralDomain + Copy> IntegralDomain for Complex<T> {}
+
+/// x^2 + 1 が係数体上で既約であること（F_p なら p を4で割って3余る素数にするなど）は使う側が保証する。
+impl<T: Field + Copy> Field for Complex<T> {}
@@ -0,0 +1,226 @@
+use crate::algebraic_structures::Ring;
+use crate::number_theory::{add_mod, is_prime, mul_mod, pow_mod, sub_mod};
+
+// 多項式の掛け算に使う畳み込み（係数列どうしの積）まわり
+
+/// 短い方の長さがこれ以下ならカラツバ法をやめて素朴な方法で計算する
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// 短い方の長さがこれ以下ならNTTを使わずに素朴な方法で計算する
+const NTT_THRESHOLD: usize = 64;
+
+/// 3つの素数でのNTTと中国剰余定理で畳み込むときに使う素数。いずれも 2^23 で割った余りが1。
+const NTT_PRIMES: [u64; 3] = [998_244_353, 167_772_161, 469_762_049];
+
+/// ```NTT_PRIMES```それぞれの```ntt_root()```
+const NTT_PRIME_ROOTS: [Option<(u64, u32)>; 3] = [
+    ntt_root(NTT_PRIMES[0]),
+    ntt_root(NTT_PRIMES[1]),
+    ntt_root(NTT_PRIMES[2]),
+];
+
+/// 法```m```でのNTTに使う1の冪根。
+///
+/// ```m```が奇素数なら、m - 1 = d 2^s（dは奇数）として位数がちょうど 2^s の元```c```を求め、```(c, s)```を返す。
+/// このとき長さ 2^s までのNTTができる。```m```が奇素数でなければ```None```。
+/// 素数判定を含むので、```ModInt```ではコンパイル時に、```DynModInt```では法を設定したときに一度だけ計算する。
+pub(crate) const fn ntt_root(m: u64) -> Option<(u64, u32)> {
+    if m < 3 || !is_prime(m) {
+        return None;
+    }
+    let s = (m - 1).trailing_zeros();
+    let d = (m - 1) >> s;
+    // 平方非剰余 z を見つければ、z^d の位数はちょうど 2^s になる
+    let mut z = 2;
+    while pow_mod(z, (m - 1) / 2, m) != m - 1 {
+        z += 1;
+    }
+    Some((pow_mod(z, d, m), s))
+}
+
+/// 素朴な O(nm) の畳み込み。どちらかが空なら空を返す。
+pub fn schoolbook<T: Ring>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut v = vec![T::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        if *x == T::zero() {
+            continue;
+        }
+        for (j, y) in b.iter().enumerate() {
+            v[i + j] += x.clone() * y.clone();
+        }
+    }
+    v
+}
+
+/// 係数列どうしの和
+fn add_slices<T: Ring>(a: &[T], b: &[T]) -> Vec<T> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut v = long.to_vec();
+    for (x, y) in v.iter_mut().zip(short) {
+        *x += y.clone();
+    }
+    v
+}
+
+/// カラツバ法による畳み込み。任意の環で使える。どちらかが空なら空を返す。
+pub fn karatsuba<T: Ring>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.len().min(b.len()) <= KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+    // 長さが2倍以上違うときは、長い方を短い方の長さずつに切って掛ける（O(max min^0.58)）。
+    // 可換とは限らないので、掛ける順は入れ替えない。
+    if a.len() >= 2 * b.len() || b.len() >= 2 * a.len() {
+        let k = a.len().min(b.len());
+        let products: Vec<Vec<T>> = if a.len() > b.len() {
+            a.chunks(k).map(|c| karatsuba(c, b)).collect()
+        } else {
+            b.chunks(k).map(|c| karatsuba(a, c)).collect()
+        };
+        let mut v = vec![T::zero(); a.len() + b.len() - 1];
+        for (i, p) in products.into_iter().enumerate() {
+            for (j, x) in p.into_iter().enumerate() {
+                v[i * k + j] += x;
+            }
+        }
+        return v;
+    }
+    // a = a0 + a1 x^k, b = b0 + b1 x^k と分けて
+    // ab = a0 b0 + ((a0 + a1)(b0 + b1) - a0 b0 - a1 b1) x^k + a1 b1 x^(2k)
+    let k = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(k.min(a.len()));
+    let (b0, b1) = b.split_at(k.min(b.len()));
+    let z0 = karatsuba(a0, b0);
+    let z2 = karatsuba(a1, b1);
+    let mut z1 = karatsuba(&add_slices(a0, a1), &add_slices(b0, b1));
+    for (x, y) in z1.iter_mut().zip(&z0) {
+        *x -= y.clone();
+    }
+    for (x, y) in z1.iter_mut().zip(&z2) {
+        *x -= y.clone();
+    }
+    let n = a.len() + b.len() - 1;
+    let mut v = vec![T::zero(); n.max(k + z1.len()).max(2 * k + z2.len())];
+    for (i, x) in z0.into_iter().enumerate() {
+        v[i] += x;
+    }
+    for (i, x) in z1.into_iter().enumerate() {
+        v[i + k] += x;
+    }
+    for (i, x) in z2.into_iter().enumerate() {
+        v[i + 2 * k] += x;
+    }
+    v.truncate(n);
+    v
+}
+
+/// 数論変換（NTT）。```root```は```ntt_root(m)```で、```a```の長さは 2^s 以下の2のべきである必要がある。
+fn ntt(a: &mut [u64], invert: bool, m: u64, root: (u64, u32)) {
+    let (c, s) = root;
+    let n = a.len();
+    // ビット反転の順に並べ替える
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        // c の位数は 2^s なので、c^(2^s / len) が1の原始len乗根
+        let mut w = pow_mod(c, 1 << (s - len.trailing_zeros()), m);
+        if invert {
+            w = pow_mod(w, m - 2, m);
+        }
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut wn = 1;
+            for (x, y) in lo.iter_mut().zip(hi.iter_mut()) {
+                let u = *x;
+                let v = mul_mod(*y, wn, m);
+                *x = add_mod(u, v, m);
+                *y = sub_mod(u, v, m);
+                wn = mul_mod(wn, w, m);
+            }
+        }
+        len <<= 1;
+    }
+    if invert {
+        let n_inv = pow_mod(n as u64, m - 2, m);
+        for x in a.iter_mut() {
+            *x = mul_mod(*x, n_inv, m);
+        }
+    }
+}
+
+/// NTTによる畳み込み。```root```は```ntt_root(m)```で、結果の長さ以上の2のべきが 2^s 以下である必要がある。
+fn ntt_convolution(a: &[u64], b: &[u64], m: u64, root: (u64, u32)) -> Vec<u64> {
+    let n = a.len() + b.len() - 1;
+    let size = n.next_power_of_two();
+    let mut fa: Vec<u64> = a.iter().map(|&x| x % m).collect();
+    let mut fb: Vec<u64> = b.iter().map(|&x| x % m).collect();
+    fa.resize(size, 0);
+    fb.resize(size, 0);
+    ntt(&mut fa, false, m, root);
+    ntt(&mut fb, false, m, root);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x = mul_mod(*x, *y, m);
+    }
+    ntt(&mut fa, true, m, root);
+    fa.truncate(n);
+    fa
+}
+
+/// 法```m```での係数列（```0```以上```m```未満）の畳み込み。```root```は前計算しておいた```ntt_root(m)```。
+///
+/// ```m```がNTTに使える素数ならそのままNTTで、そうでなくても係数が十分小さければ
+/// 3つの素数でのNTTと中国剰余定理（Garnerのアルゴリズム）で計算する。
+/// どちらも使えないときや短いときは```None```を返すので、呼ぶ側でカラツバ法などに切り替える。
+pub fn convolution_mod(a: &[u64], b: &[u64], m: u64, root: Option<(u64, u32)>) -> Option<Vec<u64>> {
+    if a.len().min(b.len()) <= NTT_THRESHOLD {
+        return None;
+    }
+    let n = a.len() + b.len() - 1;
+    let size = n.next_power_of_two();
+    if let Some((c, s)) = root {
+        if size.trailing_zeros() <= s {
+            return Some(ntt_convolution(a, b, m, (c, s)));
+        }
+    }
+    // 3つの素数の積は約 7.9 * 10^25 なので、真の係数 (m-1)^2 * min(len) がそれ未満なら復元できる
+    let bound = NTT_PRIMES.iter().fold(1u128, |acc, &p| acc * p as u128);
+    if m > 1 << 32
+        || (m - 1) as u128 * (m - 1) as u128 * a.len().min(b.len()) as u128 >= bound
+        || size > 1 << 23
+    {
+        return None;
+    }
+    let [p1, p2, p3] = NTT_PRIMES;
+    let [g1, g2, g3] = NTT_PRIME_ROOTS.map(Option::unwrap);
+    let r1 = ntt_convolution(a, b, p1, g1);
+    let r2 = ntt_convolution(a, b, p2, g2);
+    let r3 = ntt_convolution(a, b, p3, g3);
+    let p1_inv = pow_mod(p1, p2 - 2, p2);
+    let p12_inv = pow_mod(mul_mod(p1, p2, p3), p3 - 2, p3);
+    let p12 = p1 as u128 * p2 as u128;
+    let v = (0..n)
+        .map(|i| {
+            // x = x1 + x2 p1 + x3 p1 p2 となる x1, x2, x3 を順に求める
+            let x1 = r1[i];
+            let x2 = mul_mod((r2[i] + p2 - x1 % p2) % p2, p1_inv, p2);
+            let x12 = (x1 as u128 + x2 as u128 * p1 as u128) % p3 as u128;
+            let x3 = mul_mod((r3[i] + p3 - x12 as u64) % p3, p12_inv, p3);
+            let m = m as u128;
+            ((x1 as u128 + x2 as u128 * p1 as u128 % m + x3 as u128 * (p12 % m)) % m) as u64
+        })
+        .collect();
+    Some(v)
+}
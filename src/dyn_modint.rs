@@ -0,0 +1,286 @@
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, Field, IntegralDomain, Ring};
+use crate::characteristic::Characteristic;
+use crate::convolution::{convolution_mod, karatsuba, ntt_root};
+use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::number_theory::{add_mod, inverse_mod, mul_mod, pow_mod, sqrt_mod, sub_mod};
+use crate::parse::{parse_integer, strip_delimiters, trim, ParseError, ParseErrorKind};
+use crate::square_root::SquareRoot;
+use std::cell::Cell;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+thread_local! {
+    /// ```DynModInt```の元を新しく作るときに使う法。0は未設定を表す。
+    static MODULUS: Cell<u64> = const { Cell::new(0) };
+    /// ```MODULUS```に対する```ntt_root()```。法を設定したときに一度だけ計算しておく。
+    static NTT_ROOT: Cell<Option<(u64, u32)>> = const { Cell::new(None) };
+}
+
+/// 実行時に決まる法```modulus```で割った余り。Z / modulus Z の元。
+///
+/// ```Zero```や```Identity```のように引数なしで元を作る必要があるので、法はスレッドごとに共有する
+/// （```DynModInt::set_modulus()```で設定する）。
+/// 各元は作られたときの法も持っていて、法の異なる元どうしで演算しようとするとpanicする。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct DynModInt {
+    representative: u64,
+    modulus: u64,
+}
+
+impl DynModInt {
+    /// これから作る元の法を設定する。0はpanic。
+    pub fn set_modulus(m: u64) {
+        if m == 0 {
+            panic!("modulus must be positive");
+        }
+        MODULUS.with(|c| c.set(m));
+        NTT_ROOT.with(|c| c.set(ntt_root(m)));
+    }
+
+    /// 現在設定されている法を返す。未設定なら0。
+    pub fn current_modulus() -> u64 {
+        MODULUS.with(|c| c.get())
+    }
+
+    /// コンストラクタ。現在設定されている法を使い、代表元は```0```以上法未満の整数として格納。
+    /// 法が設定されていないときはpanicする。
+    pub fn new(n: u64) -> Self {
+        let m = DynModInt::current_modulus();
+        if m == 0 {
+            panic!("modulus is not set; call DynModInt::set_modulus first");
+        }
+        DynModInt {
+            representative: n % m,
+            modulus: m,
+        }
+    }
+
+    /// ```u64```型にする
+    pub fn to_int(&self) -> u64 {
+        self.representative
+    }
+
+    /// この元の法
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// 繰り返し二乗法によるべき乗の計算
+    pub fn modpow(&self, n: u64) -> Self {
+        DynModInt {
+            representative: pow_mod(self.representative, n, self.modulus),
+            modulus: self.modulus,
+        }
+    }
+
+    /// 割り算。割る元が可逆でないときは```None```を返す。
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        common_modulus(&self, &rhs);
+        rhs.inverse().map(|inv| self * inv)
+    }
+}
+
+/// 2つの元の法が一致していることを確かめて、その法を返す。一致していなければpanicする。
+fn common_modulus(a: &DynModInt, b: &DynModInt) -> u64 {
+    if a.modulus != b.modulus {
+        panic!(
+            "cannot mix elements with different moduli: {} and {}",
+            a.modulus, b.modulus
+        );
+    }
+    a.modulus
+}
+
+/// ```println!```などで見やすく表示させるため、```Display```トレイトを実装。
+impl fmt::Display for DynModInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.representative)
+    }
+}
+
+/// ```[3]```（```Display```の形式）や```3```、```-3```を、いま設定されている法で読む。
+/// 法が設定されていなければ```ParseErrorKind::ModulusNotSet```を返す。
+impl FromStr for DynModInt {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let m = DynModInt::current_modulus();
+        if m == 0 {
+            return Err(ParseError::new(0, ParseErrorKind::ModulusNotSet));
+        }
+        let (t, o) = trim(s, 0);
+        let (t, o) = strip_delimiters(t, '[', ']', o).unwrap_or((t, o));
+        let n = parse_integer(t, o)?;
+        Ok(DynModInt::new(n.rem_euclid(m as i128) as u64))
+    }
+}
+
+/// ```ModInt```と同じく```\\overline{3}```のように書く。
+impl ToLatex for DynModInt {
+    fn to_latex(&self) -> String {
+        format!("\\overline{{{}}}", self.representative)
+    }
+}
+
+impl Add for DynModInt {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let m = common_modulus(&self, &rhs);
+        DynModInt {
+            representative: add_mod(self.representative, rhs.representative, m),
+            modulus: m,
+        }
+    }
+}
+
+impl AddAssign for DynModInt {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for DynModInt {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let m = common_modulus(&self, &rhs);
+        DynModInt {
+            representative: sub_mod(self.representative, rhs.representative, m),
+            modulus: m,
+        }
+    }
+}
+
+impl SubAssign for DynModInt {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Mul for DynModInt {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let m = common_modulus(&self, &rhs);
+        DynModInt {
+            representative: mul_mod(self.representative, rhs.representative, m),
+            modulus: m,
+        }
+    }
+}
+
+impl MulAssign for DynModInt {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// 割り算の実装。割る元が可逆でないときはpanicする。
+impl Div for DynModInt {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("division by a non-unit")
+    }
+}
+
+impl DivAssign for DynModInt {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+/// unary negation の実装
+impl Neg for DynModInt {
+    type Output = Self;
+    fn neg(self) -> Self {
+        DynModInt {
+            representative: sub_mod(0, self.representative, self.modulus),
+            modulus: self.modulus,
+        }
+    }
+}
+
+/// 現在設定されている法での0を返す。
+impl Zero for DynModInt {
+    fn zero() -> Self {
+        DynModInt::new(0)
+    }
+}
+
+/// 現在設定されている法での1を返す。
+impl Identity for DynModInt {
+    fn identity() -> Self {
+        DynModInt::new(1)
+    }
+}
+
+/// 逆元の実装。拡張ユークリッドの互除法で求める。
+impl Inverse for DynModInt {
+    fn inverse(self) -> Option<DynModInt> {
+        inverse_mod(self.representative, self.modulus).map(|n| DynModInt {
+            representative: n,
+            modulus: self.modulus,
+        })
+    }
+}
+
+/// 平方根の実装。```ModInt```と同じく、法が素数でないときは（0を除いて）```None```を返し、代表元の小さい方を返す。
+impl SquareRoot for DynModInt {
+    fn square_root(self) -> Option<DynModInt> {
+        sqrt_mod(self.representative, self.modulus).map(|n| DynModInt {
+            representative: n,
+            modulus: self.modulus,
+        })
+    }
+}
+
+/// 標数として、今設定されている法を返す（```ModInt```と同様、法が素数でないときも便宜的に法を返す）。
+/// 元の法ではなくスレッドで共有している法を見るので、法を切り替えたあとに古い元について使わないこと。
+impl Characteristic for DynModInt {
+    fn characteristic() -> u64 {
+        DynModInt::current_modulus()
+    }
+}
+
+impl AdditiveGroup for DynModInt {}
+
+/// 畳み込みは、使えるときはNTTで計算する。
+impl Ring for DynModInt {
+    fn convolution(a: &[Self], b: &[Self]) -> Vec<Self> {
+        let (first, m) = match (a.first(), b.first()) {
+            (Some(x), Some(y)) => (x, common_modulus(x, y)),
+            _ => return Vec::new(),
+        };
+        // NTTでは演算子を通らないので、先頭以外の元の法もここで確かめる
+        let representative = |c: &DynModInt| {
+            common_modulus(first, c);
+            c.representative
+        };
+        let x: Vec<u64> = a.iter().map(representative).collect();
+        let y: Vec<u64> = b.iter().map(representative).collect();
+        // 今の法と違う法で作られた元なら、その場で計算し直す
+        let root = if m == DynModInt::current_modulus() {
+            NTT_ROOT.with(|c| c.get())
+        } else {
+            ntt_root(m)
+        };
+        match convolution_mod(&x, &y, m, root) {
+            Some(v) => v
+                .into_iter()
+                .map(|n| DynModInt {
+                    representative: n,
+                    modulus: m,
+                })
+                .collect(),
+            None => karatsuba(a, b),
+        }
+    }
+}
+
+impl CommutativeRing for DynModInt {}
+
+/// 法が素数のときに限り整域（体）になる。素数であることは使う側が保証する。
+impl IntegralDomain for DynModInt {}
+
+/// 法が素数のときに限り体になる。素数であることは使う側が保証する。
+impl Field for DynModInt {}
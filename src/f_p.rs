@@ -1,43 +1,225 @@
-use super::identities::Zero;
+use super::algebraic_structures::Field;
+use super::characteristic::Characteristic;
+use super::dyn_modint::DynModInt;
+use super::identities::Identity;
 use super::modint::ModInt;
 use super::polynomial::Polynomial;
+use super::roots::Roots;
 use super::solution_set::SolutionSet;
 
 use std::collections::HashSet;
+use std::hash::Hash;
 
 // 有限体 F_p (pは素数) まわりのもろもろ
 
-/// 素数判定
-pub fn is_prime(n: u64) -> bool {
-    if n == 2 {
-        return true;
+// 素数判定などは number_theory に移したが、これまでどおり f_p からも使えるようにしておく
+pub use super::number_theory::{factorize, is_prime, primitive_root};
+
+/// 以前の```solve_equation()```が固定で使っていた法。今は```ModInt<P>```の```P```で法を選ぶ。
+#[deprecated(
+    note = "solve_equation() is generic over the prime now; choose the modulus with ModInt<P> instead"
+)]
+pub const MOD: u64 = 103;
+
+/// 素体 F_p の元として扱える型。p は```Characteristic```で与え、素数である必要がある。
+///
+/// ```ModInt<P>```と```DynModInt```が実装していて、以下の方程式の求解や因数分解はどちらでも使える。
+pub trait PrimeField: Field + Characteristic + Copy + Hash {
+    /// 整数```n```を p で割った余りの元
+    fn from_u64(n: u64) -> Self;
+}
+
+impl<const P: u64> PrimeField for ModInt<P> {
+    fn from_u64(n: u64) -> Self {
+        ModInt::new(n)
     }
-    if n % 2 == 0 {
-        return false;
+}
+
+/// p は今設定されている法。
+impl PrimeField for DynModInt {
+    fn from_u64(n: u64) -> Self {
+        DynModInt::new(n)
     }
-    if n == 0 || n == 1 {
-        return false;
+}
+
+/// F_p での方程式 f(x) = 0 の解を重複度付きで求める。
+///
+/// 無平方分解で重複度を求めたあと、各因子と x^p - x の最大公約数を取って相異なる1次因子の積を取り出し、
+/// それを同次数因数分解でばらばらにする。全探索しないので 998244353 や 10^9 + 7 のような大きな素数でも使える。
+///
+/// ```f```が0のときはすべての元が解になり、p が大きいと列挙しきれないので```None```を返す。
+pub fn solve_equation<T: PrimeField>(f: Polynomial<T>) -> Option<SolutionSet<T>> {
+    // 0多項式なら```None```
+    f.strict_deg()?;
+    let p = T::characteristic();
+    let mut s = SolutionSet::new(HashSet::new());
+    let x = Polynomial::new(&[T::zero(), T::identity()]);
+    for (g, m) in Polynomial::square_free_decomposition(&f) {
+        let h = Polynomial::gcd(&(Polynomial::pow_mod(&x, p, &g) - x.clone()), &g);
+        if h.deg() == 0 {
+            continue;
+        }
+        for linear in equal_degree_factorization(&h, 1) {
+            // linear = x - r なので r = -linear(0)
+            let r = -Polynomial::evaluate(&linear, T::zero());
+            s.insert_with_multiplicity(r, m);
+        }
+    }
+    Some(s)
+}
+
+/// 根は代表元の小さい順に並べる。
+impl<const P: u64> Roots for ModInt<P> {
+    fn roots(f: &Polynomial<ModInt<P>>) -> Option<Vec<ModInt<P>>> {
+        let mut v: Vec<ModInt<P>> = solve_equation(f.clone())?.iter().map(|(r, _)| *r).collect();
+        v.sort_by_key(|r| r.to_int());
+        Some(v)
     }
-    for i in 0..n {
-        if n != 3 + 2 * i && n % (3 + 2 * i) == 0 {
-            return false;
+}
+
+/// 根は代表元の小さい順に並べる。
+impl Roots for DynModInt {
+    fn roots(f: &Polynomial<DynModInt>) -> Option<Vec<DynModInt>> {
+        let mut v: Vec<DynModInt> = solve_equation(f.clone())?.iter().map(|(r, _)| *r).collect();
+        v.sort_by_key(|r| r.to_int());
+        Some(v)
+    }
+}
+
+/// 乱択アルゴリズム用の簡単な擬似乱数生成器（xorshift）
+struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        XorShift {
+            state: seed ^ 0x2545_f491_4f6c_dd1d,
         }
-        if (3 + 2 * i) * (3 + 2 * i) >= n {
-            break;
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// 次数が```n```未満のF_p係数多項式をランダムに作る。
+    fn polynomial<T: PrimeField>(&mut self, n: usize) -> Polynomial<T> {
+        let v: Vec<T> = (0..n).map(|_| T::from_u64(self.next())).collect();
+        Polynomial::new(&v)
+    }
+}
+
+/// 次数別因数分解（distinct-degree factorization）。
+///
+/// 無平方でmonicな```f```を受け取り、```(g_d, d)```の列を返す。```g_d```は```f```の次数```d```の既約因子すべての積。
+pub fn distinct_degree_factorization<T: PrimeField>(
+    f: &Polynomial<T>,
+) -> Vec<(Polynomial<T>, usize)> {
+    let p = T::characteristic();
+    let one = Polynomial::<T>::identity();
+    let x = Polynomial::new(&[T::zero(), T::identity()]);
+    let mut factors = Vec::new();
+    let mut rest = f.clone();
+    let mut h = x.clone() % rest.clone();
+    let mut d = 1;
+    while rest.deg() >= 2 * d {
+        // h = x^(p^d) mod rest
+        h = Polynomial::pow_mod(&h, p, &rest);
+        let g = Polynomial::gcd(&(h.clone() - x.clone()), &rest);
+        if g != one {
+            rest /= g.clone();
+            h %= rest.clone();
+            factors.push((g, d));
         }
+        d += 1;
+    }
+    if rest.deg() > 0 {
+        let n = rest.deg();
+        factors.push((rest, n));
     }
-    true
+    factors
 }
 
-pub const MOD: u64 = 103; // テキトー
+/// 同次数因数分解（equal-degree factorization）。Cantor–Zassenhaus のアルゴリズム。
+///
+/// 次数```d```の相異なるmonicな既約多項式の積```f```を受け取り、その既約因子を返す。
+pub fn equal_degree_factorization<T: PrimeField>(
+    f: &Polynomial<T>,
+    d: usize,
+) -> Vec<Polynomial<T>> {
+    let p = T::characteristic();
+    let mut rng = XorShift::new(f.deg() as u64 * 1_000_003 + d as u64);
+    let mut stack = vec![f.clone()];
+    let mut factors = Vec::new();
+    while let Some(g) = stack.pop() {
+        if g.deg() <= d {
+            factors.push(g);
+            continue;
+        }
+        loop {
+            let a = rng.polynomial::<T>(g.deg());
+            if a.deg() == 0 {
+                continue;
+            }
+            let b = if p == 2 {
+                // 標数2では (p^d - 1)/2 乗の代わりにトレース a + a^2 + ... + a^(2^(d-1)) を使う
+                let mut t = a.clone();
+                let mut sum = a;
+                for _ in 1..d {
+                    t = Polynomial::pow_mod(&t, 2, &g);
+                    sum += t.clone();
+                }
+                sum
+            } else {
+                // a^((p^d - 1)/2) = Π (a^((p-1)/2))^(p^i) で計算してオーバーフローを避ける
+                let mut t = Polynomial::pow_mod(&a, (p - 1) / 2, &g);
+                let mut prod = t.clone();
+                for _ in 1..d {
+                    t = Polynomial::pow_mod(&t, p, &g);
+                    prod = prod * t.clone() % g.clone();
+                }
+                prod - Polynomial::identity()
+            };
+            let h = Polynomial::gcd(&b, &g);
+            if h.deg() > 0 && h.deg() < g.deg() {
+                stack.push(g.clone() / h.clone());
+                stack.push(h);
+                break;
+            }
+        }
+    }
+    factors
+}
 
-/// 方程式の解を全探索
-pub fn solve_equation(f: Polynomial<ModInt<MOD>>) -> SolutionSet<ModInt<MOD>> {
-    let mut s: HashSet<ModInt<MOD>> = HashSet::new();
-    for i in 0..MOD {
-        if Polynomial::evaluate(&f, ModInt::<MOD>::new(i)) == ModInt::<MOD>::zero() {
-            s.insert(ModInt::<MOD>::new(i));
+/// F_p係数多項式を、monicな既約多項式とその重複度の組```(g, m)```の列に因数分解する。最高次係数は無視する。
+///
+/// 無平方分解、次数別因数分解、同次数因数分解（Cantor–Zassenhaus）の順に行う。
+/// 並びは既約因子の次数の小さい順（同じ次数の中では重複度の小さい順）。
+pub fn factorize_polynomial<T: PrimeField>(f: &Polynomial<T>) -> Vec<(Polynomial<T>, usize)> {
+    let mut factors = Vec::new();
+    for (g, m) in Polynomial::square_free_decomposition(f) {
+        for (h, d) in distinct_degree_factorization(&g) {
+            for k in equal_degree_factorization(&h, d) {
+                factors.push((k, m));
+            }
         }
     }
-    SolutionSet::new(s)
+    factors.sort_by_key(|(g, m)| (g.deg(), *m));
+    factors
+}
+
+/// F_p係数多項式が既約かどうかの判定。定数は既約ではないとする。
+pub fn is_irreducible<T: PrimeField>(f: &Polynomial<T>) -> bool {
+    if f.deg() == 0 {
+        return false;
+    }
+    let sqf = Polynomial::square_free_decomposition(f);
+    if sqf.len() != 1 || sqf[0].1 != 1 {
+        return false;
+    }
+    let ddf = distinct_degree_factorization(&sqf[0].0);
+    ddf.len() == 1 && ddf[0].1 == f.deg()
 }
@@ -0,0 +1,194 @@
+use crate::algebraic_structures::Ring;
+use crate::matrix::Matrix;
+use crate::parse::split_terms;
+use crate::polynomial::Polynomial;
+use crate::solution_set::SolutionSet;
+use std::fmt;
+use std::hash::Hash;
+
+/// 項を並べる順番
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// 次数の低い順（1 + 2x + 3x^2）
+    Ascending,
+    /// 次数の高い順（3x^2 + 2x + 1）
+    Descending,
+}
+
+/// 指数や括弧の書き方
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// x^2 のように書く。```FromStr```で読み戻せる。
+    Plain,
+    /// x² のように上付き文字を使う。
+    Unicode,
+    /// x^{2} のように LaTeX で書く。
+    Latex,
+}
+
+/// 多項式の表示の設定。```Polynomial```の```Display```は```PolynomialFormat::new()```の設定で表示する。
+///
+/// ```PolynomialFormat::new().variable("t").order(Order::Descending).notation(Notation::Unicode)```
+/// のように設定を変えてから```polynomial()```などで文字列にする。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolynomialFormat {
+    variable: String,
+    order: Order,
+    notation: Notation,
+}
+
+impl PolynomialFormat {
+    /// 変数名 x、次数の低い順、```Notation::Plain```
+    pub fn new() -> Self {
+        Self {
+            variable: "x".to_string(),
+            order: Order::Ascending,
+            notation: Notation::Plain,
+        }
+    }
+
+    /// 変数名を変える。
+    pub fn variable(mut self, v: &str) -> Self {
+        self.variable = v.to_string();
+        self
+    }
+
+    /// 項を並べる順番を変える。
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// 指数や括弧の書き方を変える。
+    pub fn notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// 多項式を文字列にする。負の係数は ```3 - 2x``` のように引き算として書き、
+    /// 係数の1や-1は省略する。```[3]```のように括弧の中が数だけの係数は括弧を外す。
+    pub fn polynomial<T: fmt::Display + Ring>(&self, f: &Polynomial<T>) -> String {
+        self.polynomial_with(f, |c| simplify(c.to_string()))
+    }
+
+    /// 係数の書き方```render```を指定して多項式を文字列にする。LaTeX で書くときなどに使う。
+    pub fn polynomial_with<T: Ring, F: Fn(&T) -> String>(
+        &self,
+        f: &Polynomial<T>,
+        render: F,
+    ) -> String {
+        let terms: Vec<(usize, &T)> = match self.order {
+            Order::Ascending => f.terms().collect(),
+            Order::Descending => f.terms().rev().collect(),
+        };
+        if terms.is_empty() {
+            return render(&T::zero());
+        }
+        let mut s = String::new();
+        for (k, &(i, c)) in terms.iter().enumerate() {
+            let (negative, body) = self.term(i, c, terms.len() == 1, &render);
+            match (k, negative) {
+                (0, false) => {}
+                (0, true) => s.push('-'),
+                (_, false) => s.push_str(" + "),
+                (_, true) => s.push_str(" - "),
+            }
+            s.push_str(&body);
+        }
+        s
+    }
+
+    /// 解集合を文字列にする。各元は```polynomial()```で書く。
+    pub fn solution_set<T: fmt::Display + Ring + Hash>(
+        &self,
+        s: &SolutionSet<Polynomial<T>>,
+    ) -> String {
+        s.to_string_with(self.notation, |f| self.polynomial(f))
+    }
+
+    /// 行列を文字列にする。各成分は```polynomial()```で書く。
+    pub fn matrix<T: fmt::Display + Ring>(&self, a: &Matrix<Polynomial<T>>) -> String {
+        a.to_string_with(self.notation, |f| self.polynomial(f))
+    }
+
+    /// i次の項を（負号を付けて書くか, 負号を除いた部分）として返す。
+    fn term<T: Ring, F: Fn(&T) -> String>(
+        &self,
+        i: usize,
+        c: &T,
+        alone: bool,
+        render: &F,
+    ) -> (bool, String) {
+        let s = render(c);
+        let (negative, coefficient) = if is_compound(&s) {
+            // 3 + 4i のような係数は括弧でくくる
+            if alone {
+                (false, s)
+            } else {
+                match self.notation {
+                    Notation::Latex => (false, format!("\\left({}\\right)", s)),
+                    _ => (false, format!("({})", s)),
+                }
+            }
+        } else if is_negative(&s) {
+            (true, render(&-c.clone()))
+        } else {
+            (false, s)
+        };
+        if i == 0 {
+            return (negative, coefficient);
+        }
+        let unit = if negative {
+            -c.clone() == T::identity()
+        } else {
+            *c == T::identity()
+        };
+        let coefficient = if unit { String::new() } else { coefficient };
+        (negative, coefficient + &self.power(i))
+    }
+
+    /// 変数のi乗（i ≥ 1）
+    fn power(&self, i: usize) -> String {
+        if i == 1 {
+            return self.variable.clone();
+        }
+        match self.notation {
+            Notation::Plain => format!("{}^{}", self.variable, i),
+            Notation::Unicode => format!("{}{}", self.variable, superscript(i)),
+            Notation::Latex => format!("{}^{{{}}}", self.variable, i),
+        }
+    }
+}
+
+impl Default for PolynomialFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ```[3]```のように括弧の中が数だけのときは括弧を外す。
+fn simplify(s: String) -> String {
+    match s.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        Some(t) if !t.is_empty() && t.chars().all(|c| c.is_ascii_digit()) => t.to_string(),
+        _ => s,
+    }
+}
+
+/// ```-2```や```(-2)```、```(-1) / (2)```のように負号から始まる表示かどうか
+fn is_negative(s: &str) -> bool {
+    s.starts_with('-') || s.starts_with("(-")
+}
+
+/// ```3 + 4i```のように括弧の外に```+```, ```-```を含む表示かどうか
+fn is_compound(s: &str) -> bool {
+    split_terms(s, 0).is_ok_and(|terms| terms.len() > 1)
+}
+
+/// 上付き文字の数字
+fn superscript(n: usize) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    n.to_string()
+        .bytes()
+        .map(|b| DIGITS[(b - b'0') as usize])
+        .collect()
+}
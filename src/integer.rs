@@ -1,7 +1,13 @@
+use crate::algebraic_structures::{
+    AdditiveGroup, CommutativeRing, EuclideanDomain, IntegralDomain, Ring,
+};
 use crate::identities::{Identity, Zero};
 use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::parse::{parse_i64, ParseError};
 use std::fmt;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// 加減乗除など以外にもトレイトを実装するため、```i64```型と実質的には同等な```Integer```構造体を新たに定義。
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -29,6 +35,20 @@ impl fmt::Display for Integer {
     }
 }
 
+/// ```5```や```(-3)```（```Display```の形式）、```-3```を読む。
+impl FromStr for Integer {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_i64(s, 0).map(Integer::new)
+    }
+}
+
+impl ToLatex for Integer {
+    fn to_latex(&self) -> String {
+        self.value.to_string()
+    }
+}
+
 impl Add for Integer {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
@@ -110,3 +130,34 @@ impl Inverse for Integer {
         }
     }
 }
+
+impl AdditiveGroup for Integer {}
+
+impl Ring for Integer {}
+
+impl CommutativeRing for Integer {}
+
+impl IntegralDomain for Integer {}
+
+/// 絶対値をユークリッド関数とする。余りは0以上になるようにとる。
+impl EuclideanDomain for Integer {
+    fn norm(&self) -> u64 {
+        self.value.unsigned_abs()
+    }
+
+    fn div_rem(a: &Self, b: &Self) -> (Self, Self) {
+        (
+            Integer::new(a.value.div_euclid(b.value)),
+            Integer::new(a.value.rem_euclid(b.value)),
+        )
+    }
+
+    /// 負なら-1、それ以外は1
+    fn normalizing_unit(&self) -> Self {
+        if self.value < 0 {
+            Integer::new(-1)
+        } else {
+            Integer::new(1)
+        }
+    }
+}
@@ -0,0 +1,7 @@
+/// LaTeX の数式として書き出すためのトレイト。論文やノートに貼り付けるときに使う。
+///
+/// ```Polynomial<Complex<RationalNumber>>```のように入れ子になった型でも、
+/// 内側の型の```to_latex()```を使って組み立てる。
+pub trait ToLatex {
+    fn to_latex(&self) -> String;
+}
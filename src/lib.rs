@@ -1,33 +1,57 @@
+pub mod algebraic_structures;
 pub mod characteristic;
 pub mod complexification;
+pub mod convolution;
+pub mod dyn_modint;
 pub mod f_p;
+pub mod format;
 pub mod identities;
 pub mod integer;
 pub mod inverse;
+pub mod latex;
 pub mod matrix;
 pub mod modint;
+pub mod number_theory;
+pub mod parse;
 pub mod polynomial;
+pub mod power_series;
 pub mod quadratic_extension;
 pub mod quadratic_field;
 pub mod quadratic_integer;
 pub mod rational_number;
 pub mod real_number;
+pub mod roots;
+pub mod scalar;
 pub mod solution_set;
+pub mod square_root;
 
 #[cfg(test)]
 mod tests {
+    use crate::algebraic_structures::{EuclideanDomain, Field, Ring};
+    use crate::characteristic::Characteristic;
     use crate::complexification::Complex;
-    use crate::f_p::is_prime;
+    use crate::convolution::{karatsuba, schoolbook};
+    use crate::dyn_modint::DynModInt;
+    use crate::f_p::{factorize, factorize_polynomial, is_irreducible, is_prime, solve_equation};
+    use crate::format::{Notation, Order, PolynomialFormat};
     use crate::identities::{Identity, Zero};
     use crate::integer::Integer;
     use crate::inverse::Inverse;
+    use crate::latex::ToLatex;
+    use crate::matrix::{DimensionError, Matrix};
     use crate::modint::ModInt;
+    use crate::parse::{ParseError, ParseErrorKind};
     use crate::polynomial::Polynomial;
+    use crate::power_series::PowerSeries;
     use crate::quadratic_extension::QuadExt;
     use crate::quadratic_field::QuadField;
     use crate::quadratic_integer::QuadInt;
-    use crate::rational_number::RationalNumber;
+    use crate::rational_number::{rational_roots, RationalNumber};
+    use crate::real_number::RealNumber;
+    use crate::roots::Roots;
+    use crate::scalar::Scalar;
     use crate::solution_set::SolutionSet;
+    use crate::square_root::SquareRoot;
 
     use std::collections::HashSet;
 
@@ -74,6 +98,95 @@ mod tests {
         assert_eq!(n.inverse().unwrap(), m);
     }
 
+    /// 合成数を法とする ModInt の逆元。Z/12Z の単元は 1, 5, 7, 11 でいずれも自身が逆元。
+    #[test]
+    fn inverse_of_modint2() {
+        for n in 0..12 {
+            let a = ModInt::<12>::new(n);
+            match n {
+                1 | 5 | 7 | 11 => assert_eq!(a.inverse(), Some(a)),
+                _ => assert_eq!(a.inverse(), None),
+            }
+        }
+        // 3 * 67 = 201 = 2 * 100 + 1 なので Z/100Z で 3 の逆元は 67
+        assert_eq!(ModInt::<100>::new(3).inverse(), Some(ModInt::new(67)));
+    }
+
+    /// ModInt の割り算
+    #[test]
+    fn divide_modint1() {
+        let a = ModInt::<12>::new(10);
+        let b = ModInt::<12>::new(7);
+        assert_eq!(a / b, ModInt::new(10));
+        assert_eq!(a.checked_div(b), Some(ModInt::new(10)));
+        assert_eq!(a.checked_div(ModInt::new(4)), None);
+        let mut c = ModInt::<MOD2>::new(1);
+        c /= ModInt::new(2);
+        assert_eq!(c * ModInt::new(2), ModInt::identity());
+    }
+
+    /// 可逆でない元で割ろうとするとpanicする
+    #[test]
+    #[should_panic]
+    fn divide_modint2() {
+        let _ = ModInt::<12>::new(1) / ModInt::new(6);
+    }
+
+    /// 合成数を法とする ModInt で体を仮定したアルゴリズムを使うと、可逆でない元で割ろうとしたところでpanicする。
+    /// Z/6Z では 2x + 2 の最高次係数2が可逆でないので、x^2 - 1 との最大公約数は求められない。
+    #[test]
+    #[should_panic(expected = "leading coefficient of the divisor is not invertible")]
+    fn composite_modint_field1() {
+        let m = |n: u64| ModInt::<6>::new(n);
+        let f = Polynomial::new(&[-m(1), m(0), m(1)]);
+        let g = Polynomial::new(&[m(2), m(2)]);
+        let _ = Polynomial::gcd(&f, &g);
+    }
+
+    /// 大きな法での四則演算とべき乗を、多倍長整数（```num::BigUint```）で計算した結果と比べる
+    fn check_large_modint<const M: u64>() {
+        use num::BigUint;
+        let big = |n: u64| BigUint::from(n);
+        let mut x: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        };
+        for _ in 0..1000 {
+            let (a, b, e) = (next() % M, next() % M, next());
+            let (ma, mb) = (ModInt::<M>::new(a), ModInt::<M>::new(b));
+            assert_eq!(big((ma * mb).to_int()), big(a) * big(b) % big(M));
+            assert_eq!(big((ma + mb).to_int()), (big(a) + big(b)) % big(M));
+            assert_eq!(big((ma - mb).to_int()), (big(a) + big(M) - big(b)) % big(M));
+            assert_eq!(big(ma.modpow(e).to_int()), big(a).modpow(&big(e), &big(M)));
+            let mut mc = ma;
+            mc *= mb;
+            mc += ma;
+            mc -= mb;
+            assert_eq!(
+                big(mc.to_int()),
+                (big(a) * big(b) + big(a) + big(M) - big(b)) % big(M)
+            );
+        }
+    }
+
+    /// 2^32 を超える法での ModInt の演算がオーバーフローしないことの確認
+    #[test]
+    fn large_modint_test1() {
+        // 2^61 - 1
+        check_large_modint::<2_305_843_009_213_693_951>();
+        // 2^63 未満の最大の素数
+        check_large_modint::<9_223_372_036_854_775_783>();
+        // u64 に収まる最大の素数
+        check_large_modint::<18_446_744_073_709_551_557>();
+        // 2^64 - 1
+        check_large_modint::<18_446_744_073_709_551_615>();
+        let m = ModInt::<18_446_744_073_709_551_557>::new(3);
+        assert_eq!(m * m.inverse().unwrap(), ModInt::identity());
+    }
+
     /// QuadInt型の足し算と掛け算の確認
     #[test]
     fn quadratic_integer_treatment1() {
@@ -134,6 +247,61 @@ mod tests {
         assert!(is_prime(67_280_421_310_721));
     }
 
+    /// is_prime関数の確認その2。強擬素数や u64 に収まる最大の素数など。
+    #[test]
+    fn is_prime_check2() {
+        assert!(!is_prime(0));
+        assert!(!is_prime(1));
+        assert!(is_prime(2));
+        // カーマイケル数
+        assert!(!is_prime(561));
+        // 底 2, 3, 5, 7 に対する強擬素数
+        assert!(!is_prime(3_215_031_751));
+        // 2^61 - 1
+        assert!(is_prime(2_305_843_009_213_693_951));
+        // u64 に収まる最大の素数
+        assert!(is_prime(18_446_744_073_709_551_557));
+        // 2^32 に近い2つの素数の積
+        assert!(!is_prime(4_294_967_291 * 4_294_967_279));
+    }
+
+    /// 素因数分解の確認
+    #[test]
+    fn factorize_test1() {
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(1_000_000_000_000_000_000), vec![(2, 18), (5, 18)]);
+        // 2^64 - 1
+        assert_eq!(
+            factorize(18_446_744_073_709_551_615),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (6_700_417, 1)
+            ]
+        );
+        assert_eq!(
+            factorize(4_294_967_291 * 4_294_967_279),
+            vec![(4_294_967_279, 1), (4_294_967_291, 1)]
+        );
+        assert_eq!(factorize(1_000_000_007 * 1_000_000_007), vec![(MOD1, 2)]);
+        assert_eq!(factorize(998_244_353), vec![(MOD2, 1)]);
+    }
+
+    /// 2^63を超える合成数の素因数分解で、ポラード・ロー法の途中計算が溢れないことの確認
+    #[test]
+    fn factorize_test2() {
+        for n in u64::MAX - 100..=u64::MAX {
+            let factors = factorize(n);
+            assert!(factors.iter().all(|&(p, _)| is_prime(p)));
+            assert_eq!(factors.iter().fold(1u64, |acc, &(p, e)| acc * p.pow(e)), n);
+        }
+    }
+
     /// RationalNumberについて、異なる見た目の有理数どうしが同一視されているかの確認。
     #[test]
     fn equivalent_rational_numbers1() {
@@ -341,4 +509,2037 @@ mod tests {
             Complex::<RationalNumber>::new(RationalNumber::new(-2, 1), RationalNumber::new(35, 6));
         assert_eq!(x * y, z);
     }
+
+    /// 体の元を係数とする1次方程式 ax + b = 0 の解を、```Field```トレイトだけを使って求める。
+    fn solve_linear<T: Field>(a: T, b: T) -> Option<T> {
+        a.inverse().map(|inv| -b * inv)
+    }
+
+    /// ```Ring```トレイトだけを使って x^n を計算する。
+    fn ring_pow<T: Ring>(x: T, n: u32) -> T {
+        let mut ans = T::identity();
+        for _ in 0..n {
+            ans *= x.clone();
+        }
+        ans
+    }
+
+    /// ```Field```を要求する関数がModInt, RationalNumber, QuadField, Complexで使えることの確認
+    #[test]
+    fn field_trait_test1() {
+        let x = solve_linear(ModInt::<13>::new(5), ModInt::<13>::new(1));
+        assert_eq!(x, Some(ModInt::<13>::new(5)));
+        let y = solve_linear(RationalNumber::new(3, 1), RationalNumber::new(-1, 2));
+        assert_eq!(y, Some(RationalNumber::new(1, 6)));
+        assert_eq!(
+            solve_linear(RationalNumber::new(0, 1), RationalNumber::new(1, 1)),
+            None
+        );
+        // (1 + √2)^(-1) = -1 + √2
+        let a = QuadField::<0, 2>::new(RationalNumber::new(1, 1), RationalNumber::new(1, 1));
+        let b = QuadField::<0, 2>::new(RationalNumber::new(-1, 1), RationalNumber::new(1, 1));
+        assert_eq!(a.inverse(), Some(b));
+        // (1 + 2i)^(-1) = 1/5 - 2/5 i
+        let z = Complex::new(RationalNumber::new(1, 1), RationalNumber::new(2, 1));
+        let w = Complex::new(RationalNumber::new(1, 5), RationalNumber::new(-2, 5));
+        assert_eq!(z.inverse(), Some(w));
+    }
+
+    /// ```Ring```を要求する関数が多項式環でも使えることの確認。(1 + x)^3 = 1 + 3x + 3x^2 + x^3
+    #[test]
+    fn ring_trait_test1() {
+        let f = Polynomial::new(&[Integer::new(1), Integer::new(1)]);
+        let g = Polynomial::new(&[
+            Integer::new(1),
+            Integer::new(3),
+            Integer::new(3),
+            Integer::new(1),
+        ]);
+        assert_eq!(ring_pow(f, 3), g);
+        assert_eq!(
+            ring_pow(QuadInt::<0, -1>::new(0, 1), 2),
+            QuadInt::new(-1, 0)
+        );
+    }
+
+    /// Integerのユークリッド整域としての振る舞いの確認
+    #[test]
+    fn euclidean_domain_integer_test1() {
+        let a = Integer::new(-7);
+        let b = Integer::new(3);
+        assert_eq!(
+            Integer::div_rem(&a, &b),
+            (Integer::new(-3), Integer::new(2))
+        );
+        let (g, s, t) = Integer::extended_gcd(&Integer::new(240), &Integer::new(46));
+        assert_eq!(g, Integer::new(2));
+        assert_eq!(s * Integer::new(240) + t * Integer::new(46), g);
+    }
+
+    /// 定数多項式との掛け算が0にならないことの確認
+    #[test]
+    fn multiply_polynomials2() {
+        let f = Polynomial::new_constant(Integer::new(2));
+        let g = Polynomial::new(&[Integer::new(1), Integer::new(1)]);
+        let h = Polynomial::new(&[Integer::new(2), Integer::new(2)]);
+        assert_eq!(f * g, h);
+    }
+
+    /// RealNumberの逆元
+    #[test]
+    fn inverse_of_real_number1() {
+        assert_eq!(RealNumber::new(4.0).inverse(), Some(RealNumber::new(0.25)));
+        assert_eq!(RealNumber::new(0.0).inverse(), None);
+    }
+
+    /// RealNumber の等号は、演算で出てきたNaNや-0.0についても反射律などを満たす
+    #[test]
+    fn real_number_eq_test1() {
+        let inf = RealNumber::new(f64::INFINITY);
+        let nan = inf - inf;
+        assert_eq!(nan, nan);
+        assert_eq!(nan, RealNumber::new(0f64) * inf);
+        assert_eq!(-nan, nan);
+        assert_eq!(RealNumber::new(-0f64), RealNumber::zero());
+        assert_eq!(-RealNumber::zero(), RealNumber::zero());
+        assert_ne!(nan, RealNumber::zero());
+        let mut s = HashSet::new();
+        s.insert(nan);
+        s.insert(inf - inf);
+        s.insert(RealNumber::new(-0f64));
+        s.insert(RealNumber::zero());
+        assert_eq!(s.len(), 2);
+    }
+
+    /// 有理数係数多項式の余り付き割り算。
+    /// x^3 - 2x^2 - 4 = (x - 3)(x^2 + x + 3) + 5
+    #[test]
+    fn div_rem_polynomials1() {
+        let r = |n: i64| RationalNumber::new(n, 1);
+        let f = Polynomial::new(&[r(-4), r(0), r(-2), r(1)]);
+        let g = Polynomial::new(&[r(-3), r(1)]);
+        let q = Polynomial::new(&[r(3), r(1), r(1)]);
+        let rem = Polynomial::new_constant(r(5));
+        assert_eq!(Polynomial::div_rem(&f, &g), (q.clone(), rem.clone()));
+        assert_eq!(f.clone() / g.clone(), q);
+        assert_eq!(f % g, rem);
+    }
+
+    /// F_7係数多項式の割り算。F_7 では x^7 + 1 = (x + 1)^7 なので x + 1 で割り切れる。
+    #[test]
+    fn div_rem_polynomials2() {
+        let m = |n: u64| ModInt::<7>::new(n);
+        let mut v = vec![m(0); 8];
+        v[0] = m(1);
+        v[7] = m(1);
+        let f = Polynomial::new(&v);
+        let g = Polynomial::new(&[m(1), m(1)]);
+        let (q, r) = Polynomial::div_rem(&f, &g);
+        assert_eq!(q.deg(), 6);
+        assert_eq!(r.strict_deg(), None);
+        assert_eq!(q * g, f);
+    }
+
+    /// 整数係数多項式の擬除算。lc(g)^(deg f - deg g + 1) f = q g + r を確認。
+    #[test]
+    fn pseudo_div_rem_polynomials1() {
+        let n = Integer::new;
+        // f = x^3 + x + 1, g = 2x^2 + 1
+        let f = Polynomial::new(&[n(1), n(1), n(0), n(1)]);
+        let g = Polynomial::new(&[n(1), n(0), n(2)]);
+        let (q, r) = Polynomial::pseudo_div_rem(&f, &g);
+        assert_eq!(q, Polynomial::new(&[n(0), n(2)]));
+        assert_eq!(r, Polynomial::new(&[n(4), n(2)]));
+        let lc_pow = Polynomial::new_constant(n(4));
+        assert_eq!(lc_pow * f, q * g + r);
+    }
+
+    /// 最高次係数が単元であれば整数係数でも割り算できる
+    #[test]
+    fn div_rem_polynomials3() {
+        let n = Integer::new;
+        let f = Polynomial::new(&[n(-1), n(0), n(0), n(1)]);
+        let g = Polynomial::new(&[n(-1), n(1)]);
+        assert_eq!(f / g, Polynomial::new(&[n(1), n(1), n(1)]));
+    }
+
+    /// 有理数係数多項式の最大公約数・拡張ユークリッドの互除法・最小公倍数
+    #[test]
+    fn gcd_polynomials1() {
+        let r = |n: i64| RationalNumber::new(n, 1);
+        // f = 2(x - 1)(x + 2), g = (x - 1)(x - 3)
+        let f = Polynomial::new(&[r(-4), r(2), r(2)]);
+        let g = Polynomial::new(&[r(3), r(-4), r(1)]);
+        let d = Polynomial::new(&[r(-1), r(1)]);
+        assert_eq!(Polynomial::gcd(&f, &g), d);
+        let (e, s, t) = Polynomial::extended_gcd(&f, &g);
+        assert_eq!(e, d);
+        assert_eq!(s * f.clone() + t * g.clone(), d);
+        // lcm = (x - 1)(x + 2)(x - 3) = x^3 - 2x^2 - 5x + 6
+        let l = Polynomial::new(&[r(6), r(-5), r(-2), r(1)]);
+        assert_eq!(Polynomial::lcm(&f, &g), l);
+    }
+
+    /// 有理数係数多項式の無平方分解。x(x - 1)^2(x + 2)^3 を分解する。
+    #[test]
+    fn square_free_decomposition_test1() {
+        let r = |n: i64| RationalNumber::new(n, 1);
+        let x = Polynomial::new(&[r(0), r(1)]);
+        let a = Polynomial::new(&[r(-1), r(1)]);
+        let b = Polynomial::new(&[r(2), r(1)]);
+        let f = x.clone() * a.clone() * a.clone() * b.clone() * b.clone() * b.clone();
+        assert_eq!(
+            Polynomial::square_free_decomposition(&f),
+            vec![(x, 1), (a, 2), (b, 3)]
+        );
+    }
+
+    /// 標数pでの無平方分解。F_7 では x^7 + 1 = (x + 1)^7 で、微分が0になる。
+    #[test]
+    fn square_free_decomposition_test2() {
+        let m = |n: u64| ModInt::<7>::new(n);
+        let mut v = vec![m(0); 8];
+        v[0] = m(1);
+        v[7] = m(1);
+        let f = Polynomial::new(&v);
+        let g = Polynomial::new(&[m(1), m(1)]);
+        assert_eq!(Polynomial::square_free_decomposition(&f), vec![(g, 7)]);
+    }
+
+    /// 標数pでの無平方分解。F_3 で 2(x + 1)(x + 2)^2 x^3 を分解する。
+    #[test]
+    fn square_free_decomposition_test3() {
+        let m = |n: u64| ModInt::<3>::new(n);
+        let x = Polynomial::new(&[m(0), m(1)]);
+        let a = Polynomial::new(&[m(1), m(1)]);
+        let b = Polynomial::new(&[m(2), m(1)]);
+        let f = Polynomial::new_constant(m(2))
+            * a.clone()
+            * b.clone()
+            * b.clone()
+            * x.clone()
+            * x.clone()
+            * x.clone();
+        assert_eq!(
+            Polynomial::square_free_decomposition(&f),
+            vec![(a, 1), (b, 2), (x, 3)]
+        );
+    }
+
+    /// F_5 での x^4 - 1 の因数分解。1, 2, 3, 4 がすべて根になる。
+    #[test]
+    fn factorize_polynomial_test1() {
+        let m = |n: u64| ModInt::<5>::new(n);
+        let f = Polynomial::new(&[m(4), m(0), m(0), m(0), m(1)]);
+        let factors: HashSet<(Polynomial<ModInt<5>>, usize)> =
+            factorize_polynomial(&f).into_iter().collect();
+        let expected: HashSet<(Polynomial<ModInt<5>>, usize)> = (1..5)
+            .map(|a| (Polynomial::new(&[-m(a), m(1)]), 1))
+            .collect();
+        assert_eq!(factors, expected);
+    }
+
+    /// F_3 での (x^2 + 1)(x^2 + x + 2)(x + 1)^2 の因数分解。次数2の既約因子の分離を確認。
+    #[test]
+    fn factorize_polynomial_test2() {
+        let m = |n: u64| ModInt::<3>::new(n);
+        let a = Polynomial::new(&[m(1), m(0), m(1)]);
+        let b = Polynomial::new(&[m(2), m(1), m(1)]);
+        let c = Polynomial::new(&[m(1), m(1)]);
+        let f = a.clone() * b.clone() * c.clone() * c.clone();
+        let factors = factorize_polynomial(&f);
+        assert_eq!(factors.len(), 3);
+        assert_eq!(factors[0], (c, 2));
+        let rest: HashSet<(Polynomial<ModInt<3>>, usize)> = factors[1..].iter().cloned().collect();
+        assert_eq!(rest, [(a, 1), (b, 1)].iter().cloned().collect());
+    }
+
+    /// F_2 での因数分解と既約性判定。x^4 + x + 1 は既約、x^4 + 1 = (x + 1)^4。
+    #[test]
+    fn factorize_polynomial_test3() {
+        let m = |n: u64| ModInt::<2>::new(n);
+        let f = Polynomial::new(&[m(1), m(1), m(0), m(0), m(1)]);
+        assert!(is_irreducible(&f));
+        assert_eq!(factorize_polynomial(&f), vec![(f.clone(), 1)]);
+        let g = Polynomial::new(&[m(1), m(0), m(0), m(0), m(1)]);
+        assert!(!is_irreducible(&g));
+        assert_eq!(
+            factorize_polynomial(&g),
+            vec![(Polynomial::new(&[m(1), m(1)]), 4)]
+        );
+        // (x^2 + x + 1)(x^4 + x + 1)(x^4 + x^3 + 1) は次数別・同次数の分解が両方必要
+        let h = Polynomial::new(&[m(1), m(1), m(1)]);
+        let k = Polynomial::new(&[m(1), m(0), m(0), m(1), m(1)]);
+        let prod = h.clone() * f.clone() * k.clone();
+        let factors = factorize_polynomial(&prod);
+        assert_eq!(factors[0], (h, 1));
+        let rest: HashSet<(Polynomial<ModInt<2>>, usize)> = factors[1..].iter().cloned().collect();
+        assert_eq!(rest, [(f, 1), (k, 1)].iter().cloned().collect());
+    }
+
+    /// F_103 での既約性判定と、因子の積がもとの多項式（をmonicにしたもの）に戻ることの確認
+    #[test]
+    fn factorize_polynomial_test4() {
+        let m = |n: u64| ModInt::<103>::new(n);
+        // 103 は 4 で割って 3 余るので x^2 + 1 は既約
+        assert!(is_irreducible(&Polynomial::new(&[m(1), m(0), m(1)])));
+        let f = Polynomial::new(&[m(4), m(95), m(3), m(1), m(7), m(0), m(12), m(1)]);
+        let mut prod = Polynomial::identity();
+        for (g, e) in factorize_polynomial(&f) {
+            assert!(is_irreducible(&g));
+            for _ in 0..e {
+                prod *= g.clone();
+            }
+        }
+        assert_eq!(prod, f);
+    }
+
+    /// F_103 での方程式の解が全探索の結果と一致することの確認
+    #[test]
+    fn solve_equation_test1() {
+        let m = |n: u64| ModInt::<103>::new(n);
+        let f = Polynomial::new(&[m(4), m(95), m(3), m(1), m(7), m(0), m(12), m(1)]);
+        let s = solve_equation(f.clone()).unwrap();
+        for i in 0..103 {
+            let root = Polynomial::evaluate(&f, m(i)) == ModInt::zero();
+            assert_eq!(s.contains(&m(i)), root);
+        }
+    }
+
+    /// 0多項式ではすべての元が解になるので、列挙せずに```None```を返すことの確認
+    #[test]
+    fn solve_equation_test4() {
+        assert!(solve_equation(Polynomial::<ModInt<MOD2>>::zero()).is_none());
+        let m = |n: u64| ModInt::<7>::new(n);
+        let s = solve_equation(Polynomial::new(&[m(0), m(0)]))
+            .unwrap_or_else(|| SolutionSet::new((0..7).map(m).collect()));
+        assert_eq!(s.len(), 7);
+    }
+
+    /// 大きな素数 998244353 での方程式 (x - 1)^2 (x - 5)(x^2 - 3) = 0 の解と重複度。3は原始根なので平方剰余でない。
+    #[test]
+    fn solve_equation_test2() {
+        let m = |n: u64| ModInt::<MOD2>::new(n);
+        let a = Polynomial::new(&[-m(1), m(1)]);
+        let b = Polynomial::new(&[-m(5), m(1)]);
+        let c = Polynomial::new(&[-m(3), m(0), m(1)]);
+        let f = a.clone() * a * b * c;
+        let s = solve_equation(f).unwrap();
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.multiplicity(&m(1)), 2);
+        assert_eq!(s.multiplicity(&m(5)), 1);
+    }
+
+    /// 大きな素数 10^9 + 7 での方程式の解。10^9 + 7 は4で割って3余るので x^2 + 1 は根を持たない。
+    #[test]
+    fn solve_equation_test3() {
+        let m = |n: u64| ModInt::<MOD1>::new(n);
+        let a = Polynomial::new(&[-m(123_456_789), m(1)]);
+        let b = Polynomial::new(&[-m(987_654_321), m(1)]);
+        let c = Polynomial::new(&[m(1), m(0), m(1)]);
+        let f = Polynomial::new_constant(m(3)) * a * b.clone() * b * c;
+        let s = solve_equation(f).unwrap();
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.multiplicity(&m(123_456_789)), 1);
+        assert_eq!(s.multiplicity(&m(987_654_321)), 2);
+    }
+
+    /// 実行時に法を決める DynModInt の演算と、多項式の係数としての利用
+    #[test]
+    fn dyn_modint_test1() {
+        DynModInt::set_modulus(13);
+        let a = DynModInt::new(5);
+        let b = DynModInt::new(8);
+        assert_eq!(a.inverse(), Some(b));
+        assert_eq!(a + b, DynModInt::zero());
+        assert_eq!(a * b, DynModInt::identity());
+        assert_eq!(DynModInt::new(1) / a, b);
+        assert_eq!(a.modpow(12), DynModInt::identity());
+        // (x + 5)(x + 8) = x^2 + 1 (mod 13)
+        let f = Polynomial::new(&[a, DynModInt::identity()]);
+        let g = Polynomial::new(&[b, DynModInt::identity()]);
+        let h = Polynomial::new(&[DynModInt::new(1), DynModInt::zero(), DynModInt::new(1)]);
+        assert_eq!(f.clone() * g.clone(), h);
+        assert_eq!(Polynomial::gcd(&h, &f), f);
+        // 合成数の法
+        DynModInt::set_modulus(12);
+        assert_eq!(DynModInt::new(5).inverse(), Some(DynModInt::new(5)));
+        assert_eq!(DynModInt::new(4).inverse(), None);
+        assert_eq!(DynModInt::characteristic(), 12);
+    }
+
+    /// DynModInt係数の多項式でも、標数を使う無平方分解、因数分解、方程式の求解ができることの確認
+    #[test]
+    fn dyn_modint_test3() {
+        DynModInt::set_modulus(13);
+        let m = |n: i64| DynModInt::new(n.rem_euclid(13) as u64);
+        // (x - 1)^2 (x - 3)(x^2 + 2)  (x^2 + 2 は F_13 上既約)
+        let f = Polynomial::new(&[m(-1), m(1)])
+            * Polynomial::new(&[m(-1), m(1)])
+            * Polynomial::new(&[m(-3), m(1)])
+            * Polynomial::new(&[m(2), m(0), m(1)]);
+        assert_eq!(
+            Polynomial::square_free_decomposition(&f),
+            vec![
+                (
+                    Polynomial::new(&[m(-3), m(1)]) * Polynomial::new(&[m(2), m(0), m(1)]),
+                    1
+                ),
+                (Polynomial::new(&[m(-1), m(1)]), 2)
+            ]
+        );
+        assert_eq!(
+            factorize_polynomial(&f),
+            vec![
+                (Polynomial::new(&[m(-3), m(1)]), 1),
+                (Polynomial::new(&[m(-1), m(1)]), 2),
+                (Polynomial::new(&[m(2), m(0), m(1)]), 1)
+            ]
+        );
+        assert!(is_irreducible(&Polynomial::new(&[m(2), m(0), m(1)])));
+        let s = solve_equation(f).unwrap();
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.multiplicity(&m(1)), 2);
+        assert_eq!(s.multiplicity(&m(3)), 1);
+    }
+
+    /// 法の異なる DynModInt どうしの演算はpanicする
+    #[test]
+    #[should_panic]
+    fn dyn_modint_test2() {
+        DynModInt::set_modulus(7);
+        let a = DynModInt::new(3);
+        DynModInt::set_modulus(11);
+        let b = DynModInt::new(3);
+        let _ = a + b;
+    }
+
+    /// 畳み込み（NTT）に渡す係数列の途中に法の異なる元が混ざっていてもpanicする
+    #[test]
+    #[should_panic(expected = "cannot mix elements with different moduli")]
+    fn dyn_modint_test4() {
+        DynModInt::set_modulus(1_000_000_007);
+        let other = DynModInt::new(2);
+        DynModInt::set_modulus(998_244_353);
+        let mut a = vec![DynModInt::new(1); 100];
+        a[40] = other;
+        let b = vec![DynModInt::new(1); 100];
+        DynModInt::convolution(&a, &b);
+    }
+
+    /// テスト用の擬似乱数で長さ```n```の係数列を作る
+    fn random_modints<const M: u64>(n: usize, seed: u64) -> Vec<ModInt<M>> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                ModInt::new(x)
+            })
+            .collect()
+    }
+
+    /// NTT（998244353）、3つの素数でのNTT + 中国剰余定理（10^9 + 7）、
+    /// カラツバ法（2^61 - 1）のそれぞれで、多項式の積が素朴な方法と一致することの確認
+    #[test]
+    fn multiply_polynomials_ntt1() {
+        let a = random_modints::<MOD2>(300, 1);
+        let b = random_modints::<MOD2>(500, 2);
+        let f = Polynomial::new(&a) * Polynomial::new(&b);
+        assert_eq!(f, Polynomial::new(&schoolbook(&a, &b)));
+
+        let a = random_modints::<MOD1>(300, 3);
+        let b = random_modints::<MOD1>(500, 4);
+        let f = Polynomial::new(&a) * Polynomial::new(&b);
+        assert_eq!(f, Polynomial::new(&schoolbook(&a, &b)));
+
+        let a = random_modints::<2_305_843_009_213_693_951>(300, 5);
+        let b = random_modints::<2_305_843_009_213_693_951>(500, 6);
+        let f = Polynomial::new(&a) * Polynomial::new(&b);
+        assert_eq!(f, Polynomial::new(&schoolbook(&a, &b)));
+    }
+
+    /// 2^63を超えるNTT素数 2^64 - 2^32 + 1 でも、NTTの途中で桁あふれせず素朴な方法と一致することの確認
+    #[test]
+    fn multiply_polynomials_ntt4() {
+        const P: u64 = 18_446_744_069_414_584_321;
+        let a = random_modints::<P>(100, 12);
+        let f = Polynomial::new(&a);
+        assert_eq!(f.clone() * f, Polynomial::new(&schoolbook(&a, &a)));
+    }
+
+    /// 整数係数でカラツバ法が素朴な方法と一致することの確認（長さが大きく違う場合も含む）
+    #[test]
+    fn karatsuba_test1() {
+        let a: Vec<Integer> = (0..200)
+            .map(|i| Integer::new((i * 37 % 101) - 50))
+            .collect();
+        let b: Vec<Integer> = (0..77).map(|i| Integer::new((i * 53 % 97) - 48)).collect();
+        assert_eq!(karatsuba(&a, &b), schoolbook(&a, &b));
+        assert_eq!(karatsuba(&b, &a), schoolbook(&b, &a));
+        assert_eq!(karatsuba(&a[..33], &a), schoolbook(&a[..33], &a));
+    }
+
+    /// 長さが大きく違うときのカラツバ法（長い方を切って掛ける）と、切らずに分ける場合の確認
+    #[test]
+    fn karatsuba_test2() {
+        let a = random_modints::<MOD2>(5000, 3);
+        let b = random_modints::<MOD2>(40, 4);
+        assert_eq!(karatsuba(&a, &b), schoolbook(&a, &b));
+        assert_eq!(karatsuba(&b, &a), schoolbook(&b, &a));
+        assert_eq!(
+            karatsuba(&a[..100], &a[..60]),
+            schoolbook(&a[..100], &a[..60])
+        );
+    }
+
+    /// 次数 10^5 程度の多項式の積。ランダムな点での値 f(r)g(r) = (fg)(r) で検算する。
+    #[test]
+    fn multiply_polynomials_ntt2() {
+        let f = Polynomial::new(&random_modints::<MOD2>(100_000, 7));
+        let g = Polynomial::new(&random_modints::<MOD2>(100_000, 8));
+        let r = ModInt::<MOD2>::new(123_456_789);
+        let h = f.clone() * g.clone();
+        assert_eq!(h.deg(), f.deg() + g.deg());
+        assert_eq!(
+            Polynomial::evaluate(&h, r),
+            Polynomial::evaluate(&f, r) * Polynomial::evaluate(&g, r)
+        );
+    }
+
+    /// 有理数・実数・ModInt の平方根
+    #[test]
+    fn square_root_test1() {
+        let r = |n: i64, d: u64| RationalNumber::new(n, d);
+        assert_eq!(RationalNumber::new(-4, 9).square_root(), None);
+        assert_eq!(RationalNumber::new(4, 9).square_root(), Some(r(2, 3)));
+        assert_eq!(RationalNumber::new(2, 1).square_root(), None);
+        assert_eq!(
+            RealNumber::new(2.25).square_root(),
+            Some(RealNumber::new(1.5))
+        );
+        assert_eq!(RealNumber::new(-1.0).square_root(), None);
+        assert_eq!(
+            ModInt::<MOD1>::new(2).square_root().map(|x| x * x),
+            Some(ModInt::new(2))
+        );
+        assert_eq!(ModInt::<MOD1>::new(5).square_root(), None);
+        // 998244353 - 1 は 2^23 で割り切れるので、トネリ–シャンクスのループを何周も回る
+        for x in random_modints::<MOD2>(100, 13) {
+            let y = (x * x).square_root().unwrap();
+            assert!(y == x || y == -x);
+        }
+    }
+
+    /// 法が合成数のときの平方根は、止まらずに```None```を返す
+    #[test]
+    fn square_root_composite_test1() {
+        assert_eq!(ModInt::<9>::new(1).square_root(), None);
+        assert_eq!(ModInt::<9>::new(0).square_root(), Some(ModInt::new(0)));
+        assert_eq!(ModInt::<15>::new(4).square_root(), None);
+        DynModInt::set_modulus(9);
+        assert_eq!(DynModInt::new(4).square_root(), None);
+    }
+
+    /// 実行時に決めた法でもNTTが使われ、結果が素朴な方法と一致することの確認
+    #[test]
+    fn multiply_polynomials_ntt3() {
+        for &m in &[MOD2, MOD1] {
+            DynModInt::set_modulus(m);
+            let a: Vec<DynModInt> = (0..300u64).map(|i| DynModInt::new(i * i + 7)).collect();
+            let b: Vec<DynModInt> = (0..400u64).map(|i| DynModInt::new(i * 31 + 5)).collect();
+            let f = Polynomial::new(&a) * Polynomial::new(&b);
+            assert_eq!(f, Polynomial::new(&schoolbook(&a, &b)));
+        }
+    }
+
+    /// 形式的冪級数の逆数。(1 - x)^(-1) = 1 + x + x^2 + ... と、有理数係数・複素数係数での確認
+    #[test]
+    fn power_series_inverse_test1() {
+        let m = |n: u64| ModInt::<MOD2>::new(n);
+        let f = PowerSeries::<ModInt<MOD2>, 8>::new(&[m(1), -m(1)]);
+        assert_eq!(f.inverse(), Some(PowerSeries::new(&[m(1); 8])));
+        let g = PowerSeries::<ModInt<MOD2>, 8>::new(&[m(0), m(1)]);
+        assert_eq!(g.inverse(), None);
+
+        let r = |n: i64| RationalNumber::new(n, 1);
+        // (2 + x)^(-1) = 1/2 - x/4 + x^2/8 - ...
+        let f = PowerSeries::<RationalNumber, 4>::new(&[r(2), r(1)]);
+        let g = PowerSeries::new(&[
+            RationalNumber::new(1, 2),
+            RationalNumber::new(-1, 4),
+            RationalNumber::new(1, 8),
+            RationalNumber::new(-1, 16),
+        ]);
+        assert_eq!(f.inverse(), Some(g));
+
+        // (1 + ix)^(-1) = 1 - ix - x^2 + ix^3 + ...
+        let c = |a: i64, b: i64| Complex::new(r(a), r(b));
+        let f = PowerSeries::<Complex<RationalNumber>, 5>::new(&[c(1, 0), c(0, 1)]);
+        let g = PowerSeries::new(&[c(1, 0), c(0, -1), c(-1, 0), c(0, 1), c(1, 0)]);
+        assert_eq!(f.inverse(), Some(g));
+    }
+
+    /// exp(x) = Σ x^k / k! と、log(exp(f)) = f の確認
+    #[test]
+    fn power_series_exp_log_test1() {
+        let r = |n: i64, d: u64| RationalNumber::new(n, d);
+        let x = PowerSeries::<RationalNumber, 6>::new(&[r(0, 1), r(1, 1)]);
+        let e = PowerSeries::new(&[r(1, 1), r(1, 1), r(1, 2), r(1, 6), r(1, 24), r(1, 120)]);
+        assert_eq!(x.exp(), Some(e.clone()));
+        assert_eq!(e.log(), Some(x.clone()));
+        assert_eq!(e.clone().exp(), None);
+        assert_eq!(x.log(), None);
+
+        let f = PowerSeries::<ModInt<MOD2>, 1000>::new(&random_modints::<MOD2>(1000, 11));
+        let g = f.clone() - PowerSeries::new(&[f.coeff(0)]);
+        assert_eq!(g.exp().unwrap().log(), Some(g));
+    }
+
+    /// 平方根とべき乗の確認
+    #[test]
+    fn power_series_sqrt_pow_test1() {
+        let m = |n: u64| ModInt::<MOD2>::new(n);
+        let f = PowerSeries::<ModInt<MOD2>, 300>::new(&random_modints::<MOD2>(300, 12));
+        let f = f.clone() - PowerSeries::new(&[f.coeff(0) - m(1)]);
+        let h = f.sqrt().unwrap();
+        assert_eq!(h.clone() * h, f);
+        // x^2 (1 + x) の平方根は x (1 + x/2 - x^2/8 + ...)
+        let g = PowerSeries::<ModInt<MOD2>, 4>::new(&[m(0), m(0), m(1), m(1)]);
+        let half = m(2).inverse().unwrap();
+        assert_eq!(g.sqrt(), Some(PowerSeries::new(&[m(0), m(1), half])));
+        assert_eq!(
+            PowerSeries::<ModInt<MOD2>, 4>::new(&[m(0), m(1)]).sqrt(),
+            None
+        );
+        // 最低次の係数が1でなくても、その係数が平方根を持てばよい（3は平方非剰余、4は平方剰余）
+        let three = PowerSeries::new(&[m(3)]);
+        assert_eq!((three.clone() * f.clone()).sqrt(), None);
+        let k = (three.clone() * three * f.clone()).sqrt().unwrap();
+        assert_eq!(k.clone() * k, PowerSeries::new(&[m(9)]) * f);
+        // べき乗は掛け算を繰り返したものと一致する
+        let p = PowerSeries::<ModInt<MOD2>, 50>::new(&[m(0), m(0), m(3), m(5), m(7)]);
+        assert_eq!(p.pow(4), p.clone() * p.clone() * p.clone() * p.clone());
+        assert_eq!(p.pow(30), PowerSeries::zero());
+        assert_eq!(p.pow(0), PowerSeries::identity());
+        // (1 + x)^5 の係数は二項係数
+        let q = PowerSeries::<ModInt<MOD2>, 7>::new(&[m(1), m(1)]);
+        assert_eq!(
+            q.pow(5),
+            PowerSeries::new(&[m(1), m(5), m(10), m(10), m(5), m(1)])
+        );
+    }
+
+    /// 有理数係数の冪級数の平方根。最低次の係数が平方数でない有理数なら```None```
+    #[test]
+    fn power_series_sqrt_test1() {
+        let r = |n: i64, d: u64| RationalNumber::new(n, d);
+        // 4 + 4x + x^2 = (2 + x)^2
+        let f = PowerSeries::<RationalNumber, 5>::new(&[r(4, 1), r(4, 1), r(1, 1)]);
+        assert_eq!(f.sqrt(), Some(PowerSeries::new(&[r(2, 1), r(1, 1)])));
+        // x^2 (9/4 - 3x + x^2) = x^2 (3/2 - x)^2
+        let g =
+            PowerSeries::<RationalNumber, 6>::new(&[r(0, 1), r(0, 1), r(9, 4), r(-3, 1), r(1, 1)]);
+        assert_eq!(
+            g.sqrt(),
+            Some(PowerSeries::new(&[r(0, 1), r(3, 2), r(-1, 1)]))
+        );
+        assert_eq!(
+            PowerSeries::<RationalNumber, 5>::new(&[r(2, 1), r(1, 1)]).sqrt(),
+            None
+        );
+        assert_eq!(
+            PowerSeries::<RationalNumber, 5>::new(&[r(-4, 1)]).sqrt(),
+            None
+        );
+        // 法が合成数だと最低次の係数の平方根が求まらないので```None```
+        assert_eq!(
+            PowerSeries::<ModInt<9>, 4>::new(&[ModInt::new(1), ModInt::new(2)]).sqrt(),
+            None
+        );
+    }
+
+    /// 標数が精度以下の係数体でも、べき乗は（繰り返し二乗法で）計算できることの確認
+    #[test]
+    fn power_series_pow_test1() {
+        let m = |n: u64| ModInt::<7>::new(n);
+        let f = PowerSeries::<ModInt<7>, 10>::new(&[m(1), m(1)]);
+        assert_eq!(f.pow(2), PowerSeries::new(&[m(1), m(2), m(1)]));
+        // 標数7なので (1 + x)^7 = 1 + x^7
+        assert_eq!(
+            f.pow(7),
+            PowerSeries::new(&[m(1), m(0), m(0), m(0), m(0), m(0), m(0), m(1)])
+        );
+        let g = PowerSeries::<ModInt<7>, 10>::new(&[m(0), m(3), m(5), m(2)]);
+        assert_eq!(g.pow(3), g.clone() * g.clone() * g);
+    }
+
+    /// 標数が精度より小さいと log, exp は None。標数が精度以上ならぎりぎりでも計算できる
+    #[test]
+    fn power_series_log_exp_test1() {
+        let m = |n: u64| ModInt::<7>::new(n);
+        let f = PowerSeries::<ModInt<7>, 10>::new(&[m(0), m(1)]);
+        assert_eq!(f.exp(), None);
+        assert_eq!((f + PowerSeries::identity()).log(), None);
+
+        let m = |n: u64| ModInt::<11>::new(n);
+        let g = PowerSeries::<ModInt<11>, 10>::new(&[m(0), m(1), m(3)]);
+        let e = g.exp().unwrap();
+        assert_eq!(e.log(), Some(g));
+    }
+
+    /// 合成・微分・積分の確認。exp(log(1 + x)) = 1 + x
+    #[test]
+    fn power_series_compose_test1() {
+        let r = |n: i64, d: u64| RationalNumber::new(n, d);
+        let x = PowerSeries::<RationalNumber, 6>::new(&[r(0, 1), r(1, 1)]);
+        let exp_x = x.exp().unwrap();
+        let log_1_x = (x.clone() + PowerSeries::identity()).log().unwrap();
+        assert_eq!(
+            exp_x.compose(&log_1_x),
+            Some(x.clone() + PowerSeries::identity())
+        );
+        assert_eq!(exp_x.compose(&exp_x), None);
+        // 微分すると5次の係数は分からなくなるので0になる
+        let top = PowerSeries::new(&[r(0, 1), r(0, 1), r(0, 1), r(0, 1), r(0, 1), r(1, 120)]);
+        assert_eq!(exp_x.derivative(), exp_x.clone() - top);
+        assert_eq!(
+            exp_x.derivative().integral() + PowerSeries::identity(),
+            exp_x
+        );
+        assert_eq!(format!("{}", x), "x + O(x^6)");
+    }
+
+    /// 有理数係数での補間。(0, 1), (1, 3), (2, 7) を通る2次式は x^2 + x + 1
+    #[test]
+    fn interpolate_test1() {
+        let r = |n: i64| RationalNumber::new(n, 1);
+        let points = [(r(0), r(1)), (r(1), r(3)), (r(2), r(7))];
+        let f = Polynomial::new(&[r(1), r(1), r(1)]);
+        assert_eq!(Polynomial::interpolate(&points), f);
+        assert_eq!(
+            Polynomial::multipoint_evaluate(&f, &[r(-1), r(3)]),
+            vec![r(1), r(13)]
+        );
+    }
+
+    /// 点が多いときの高速な多点評価と補間。ホーナー法での値と一致し、補間でもとに戻ることの確認
+    #[test]
+    fn interpolate_test2() {
+        let m = |n: u64| ModInt::<MOD2>::new(n);
+        let f = Polynomial::new(&random_modints::<MOD2>(700, 21));
+        let xs: Vec<ModInt<MOD2>> = (0..700).map(|i| m(i * i + 3)).collect();
+        let ys = Polynomial::multipoint_evaluate(&f, &xs);
+        for (x, y) in xs.iter().zip(&ys) {
+            assert_eq!(Polynomial::evaluate(&f, *x), *y);
+        }
+        let points: Vec<(ModInt<MOD2>, ModInt<MOD2>)> = xs.into_iter().zip(ys).collect();
+        assert_eq!(Polynomial::interpolate(&points), f);
+    }
+
+    /// x座標が重複していると補間できない
+    #[test]
+    #[should_panic]
+    fn interpolate_test3() {
+        let m = |n: u64| ModInt::<13>::new(n);
+        Polynomial::interpolate(&[(m(1), m(2)), (m(14), m(3))]);
+    }
+
+    /// 多項式の合成・平行移動・f(cx)・係数の反転
+    #[test]
+    fn compose_polynomials_test1() {
+        let n = Integer::new;
+        // f = x^2 + 1, g = 2x - 1
+        let f = Polynomial::new(&[n(1), n(0), n(1)]);
+        let g = Polynomial::new(&[n(-1), n(2)]);
+        // f(g(x)) = 4x^2 - 4x + 2
+        assert_eq!(
+            Polynomial::compose(&f, &g),
+            Polynomial::new(&[n(2), n(-4), n(4)])
+        );
+        // f(x + 3) = x^2 + 6x + 10
+        assert_eq!(
+            Polynomial::shift(&f, n(3)),
+            Polynomial::new(&[n(10), n(6), n(1)])
+        );
+        // f(2x) = 4x^2 + 1
+        assert_eq!(
+            Polynomial::scale(&f, n(2)),
+            Polynomial::new(&[n(1), n(0), n(4)])
+        );
+        // x^3 (1/x + 2/x^3) = x^2 + 2 を逆に並べる
+        let h = Polynomial::new(&[n(2), n(0), n(1), n(0)]);
+        assert_eq!(
+            Polynomial::reverse(&h),
+            Polynomial::new(&[n(1), n(0), n(2)])
+        );
+    }
+
+    /// テイラー展開。x^3 = 1 + 3(x - 1) + 3(x - 1)^2 + (x - 1)^3
+    #[test]
+    fn taylor_coefficients_test1() {
+        let r = |n: i64| RationalNumber::new(n, 1);
+        let f = Polynomial::new(&[r(0), r(0), r(0), r(1)]);
+        assert_eq!(
+            Polynomial::taylor_coefficients(&f, r(1)),
+            vec![r(1), r(3), r(3), r(1)]
+        );
+        // x^2 - 2x + 1 = (x - 1)^2 は 1 のまわりで定数項と1次の項が0
+        let g = Polynomial::new(&[r(1), r(-2), r(1)]);
+        assert_eq!(
+            Polynomial::taylor_coefficients(&g, r(1)),
+            vec![r(0), r(0), r(1)]
+        );
+    }
+
+    /// 行列の代入。A = [[1, 2], [3, 4]] は特性多項式 x^2 - 5x - 2 の根になる（ケイリー・ハミルトンの定理）
+    #[test]
+    fn evaluate_matrix_test1() {
+        let n = Integer::new;
+        let mut a = Matrix::new(2, 2);
+        a.change_entry(0, 0, n(1));
+        a.change_entry(0, 1, n(2));
+        a.change_entry(1, 0, n(3));
+        a.change_entry(1, 1, n(4));
+        let f = Polynomial::new(&[n(-2), n(-5), n(1)]);
+        assert_eq!(Polynomial::evaluate_matrix(&f, &a), Matrix::new(2, 2));
+        let x = Polynomial::new(&[n(0), n(1)]);
+        assert_eq!(Polynomial::evaluate_matrix(&x, &a), a);
+    }
+
+    /// 係数の取り出しや根からの構成など
+    #[test]
+    fn polynomial_accessors_test1() {
+        let n = Integer::new;
+        // (x - 1)(x - 2)(x + 3) = x^3 - 7x + 6
+        let f = Polynomial::from_roots(&[n(1), n(2), n(-3)]);
+        assert_eq!(f, Polynomial::new(&[n(6), n(-7), n(0), n(1)]));
+        assert_eq!(f.coeff(1), n(-7));
+        assert_eq!(f.coeff(10), n(0));
+        assert_eq!(f[0], n(6));
+        assert_eq!(*f.leading_coefficient(), n(1));
+        assert_eq!(
+            f.terms().map(|(i, c)| (i, *c)).collect::<Vec<_>>(),
+            vec![(0, n(6)), (1, n(-7)), (3, n(1))]
+        );
+        let g = Polynomial::x() * Polynomial::x() * Polynomial::x() - Polynomial::monomial(n(7), 1)
+            + Polynomial::new_constant(n(6));
+        assert_eq!(f, g);
+        let h: Polynomial<Integer> = vec![n(6), n(-7), n(0), n(1), n(0)].into_iter().collect();
+        assert_eq!(f, h);
+        assert_eq!(Vec::from(f), vec![n(6), n(-7), n(0), n(1)]);
+    }
+
+    /// monicにする・冪級数との行き来
+    #[test]
+    fn polynomial_accessors_test2() {
+        let r = |n: i64| RationalNumber::new(n, 1);
+        let f = Polynomial::new(&[r(1), r(2), r(4)]);
+        assert_eq!(
+            f.monic(),
+            Polynomial::new(&[RationalNumber::new(1, 4), RationalNumber::new(1, 2), r(1)])
+        );
+        assert_eq!(
+            Polynomial::<RationalNumber>::zero().monic(),
+            Polynomial::zero()
+        );
+        let s: PowerSeries<RationalNumber, 2> = PowerSeries::from(f.clone());
+        assert_eq!(s.to_polynomial(), Polynomial::new(&[r(1), r(2)]));
+        let t = PowerSeries::<RationalNumber, 5>::from_polynomial(&f);
+        assert_eq!(t.to_polynomial(), f);
+    }
+
+    /// -bound 以上 bound 以下の整数を乱数で作る（xorshift）
+    fn random_i64s(n: usize, seed: u64, bound: i64) -> Vec<i64> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                (x % (2 * bound as u64 + 1)) as i64 - bound
+            })
+            .collect()
+    }
+
+    /// 整数・剰余類・有理数・実数の読み取り。```Display```の出力を読むと元に戻ることも確認
+    #[test]
+    fn parse_scalars_test1() {
+        assert_eq!("(-3)".parse(), Ok(Integer::new(-3)));
+        assert_eq!(" -3 ".parse(), Ok(Integer::new(-3)));
+        assert_eq!("[3]".parse(), Ok(ModInt::<7>::new(3)));
+        assert_eq!("-1".parse(), Ok(ModInt::<7>::new(6)));
+        assert_eq!("-7/21".parse(), Ok(RationalNumber::new(-1, 3)));
+        assert_eq!("(4) / (-6)".parse(), Ok(RationalNumber::new(-2, 3)));
+        assert_eq!("5".parse(), Ok(RationalNumber::new(5, 1)));
+        assert_eq!("-1.5".parse(), Ok(RealNumber::new(-1.5)));
+
+        let v = random_i64s(200, 12345, 1_000_000);
+        for w in v.chunks(2) {
+            let n = Integer::new(w[0]);
+            assert_eq!(n.to_string().parse(), Ok(n));
+            let m = ModInt::<MOD1>::new(w[0].unsigned_abs());
+            assert_eq!(m.to_string().parse(), Ok(m));
+            let r = RationalNumber::new(w[0], w[1].unsigned_abs() + 1);
+            assert_eq!(r.to_string().parse(), Ok(r));
+            let x = RealNumber::new(w[0] as f64 / 64.0);
+            assert_eq!(x.to_string().parse(), Ok(x));
+        }
+    }
+
+    /// 複素数・2次の拡大の元・```DynModInt```の読み取り
+    #[test]
+    fn parse_extensions_test1() {
+        let n = Integer::new;
+        let r = |a: i64, b: u64| RationalNumber::new(a, b);
+        assert_eq!("3 - 5i".parse(), Ok(Complex::new(n(3), n(-5))));
+        assert_eq!("-i".parse(), Ok(Complex::new(n(0), n(-1))));
+        assert_eq!("2i + 7".parse(), Ok(Complex::new(n(7), n(2))));
+        assert_eq!("[1 + 1x]".parse(), Ok(QuadInt::<0, 2>::new(1, 1)));
+        assert_eq!("[2 - x]".parse(), Ok(QuadInt::<0, 2>::new(2, -1)));
+        assert_eq!(
+            "[1/2 - 3/4x]".parse(),
+            Ok(QuadField::<0, 2>::new(r(1, 2), r(-3, 4)))
+        );
+        assert_eq!(
+            QuadExt::parse("[2 - 3x]", n(0), n(2)),
+            Ok(QuadExt::new(n(2), n(-3), n(0), n(2)))
+        );
+        assert_eq!(
+            "[2 - 3x] mod x^2 - 2".parse(),
+            Ok(QuadExt::new(n(2), n(-3), n(0), n(2)))
+        );
+        assert_eq!(
+            "[2 - 3x]"
+                .parse::<QuadExt<Integer>>()
+                .map_err(|e| e.kind().clone()),
+            Err(ParseErrorKind::ModulusNotSet)
+        );
+        assert_eq!(
+            "[2 - 3x] mod 2x^2 - 2"
+                .parse::<QuadExt<Integer>>()
+                .map_err(|e| e.position()),
+            Err(12)
+        );
+
+        let v = random_i64s(200, 777, 1000);
+        for w in v.chunks(4) {
+            let z = Complex::new(n(w[0]), n(w[1]));
+            assert_eq!(z.to_string().parse(), Ok(z));
+            let q = Complex::new(r(w[0], 3), r(w[1], 5));
+            assert_eq!(q.to_string().parse(), Ok(q));
+            let a = QuadInt::<1, 1>::new(w[2], w[3]);
+            assert_eq!(a.to_string().parse(), Ok(a));
+            let b = QuadField::<0, -1>::new(r(w[0], 7), r(w[3], 2));
+            assert_eq!(b.to_string().parse(), Ok(b));
+            let c = QuadExt::new(
+                ModInt::<7>::new(w[0].unsigned_abs()),
+                ModInt::new(w[1].unsigned_abs()),
+                ModInt::new(1),
+                ModInt::new(3),
+            );
+            assert_eq!(
+                QuadExt::parse(&c.to_string(), ModInt::new(1), ModInt::new(3)),
+                Ok(c)
+            );
+            assert_eq!(format!("{:#}", c).parse(), Ok(c));
+        }
+
+        DynModInt::set_modulus(13);
+        assert_eq!("[20]".parse::<DynModInt>(), Ok(DynModInt::new(7)));
+        assert_eq!("-1".parse::<DynModInt>(), Ok(DynModInt::new(12)));
+    }
+
+    /// 多項式と冪級数の読み取り
+    #[test]
+    fn parse_polynomials_test1() {
+        let n = Integer::new;
+        assert_eq!(
+            "2 + 3x + 6x^2 + 8x^4".parse(),
+            Ok(Polynomial::new(&[n(2), n(3), n(6), n(0), n(8)]))
+        );
+        assert_eq!(
+            "x^3 - 2x + 1".parse(),
+            Ok(Polynomial::new(&[n(1), n(-2), n(0), n(1)]))
+        );
+        assert_eq!(
+            "-x^2 + 3*x - x^2".parse(),
+            Ok(Polynomial::new(&[n(0), n(3), n(-2)]))
+        );
+        assert_eq!("0".parse(), Ok(Polynomial::<Integer>::zero()));
+        assert_eq!(
+            "[1 + 1x]x^2 + [3]".parse(),
+            Ok(Polynomial::new(&[
+                QuadInt::<0, 2>::new(3, 0),
+                QuadInt::zero(),
+                QuadInt::new(1, 1)
+            ]))
+        );
+        assert_eq!(
+            "1 + 2x + O(x^3)".parse(),
+            Ok(PowerSeries::<Integer, 3>::new(&[n(1), n(2)]))
+        );
+        assert_eq!(
+            "1 + 2x + 5x^7".parse(),
+            Ok(PowerSeries::<Integer, 3>::new(&[n(1), n(2)]))
+        );
+        assert_eq!("O(x^3)".parse(), Ok(PowerSeries::<Integer, 3>::zero()));
+
+        let v = random_i64s(300, 2468, 50);
+        for w in v.chunks(6) {
+            let f = Polynomial::new(&w.iter().map(|&a| n(a)).collect::<Vec<_>>());
+            assert_eq!(f.to_string().parse(), Ok(f));
+            let g = Polynomial::new(
+                &w.iter()
+                    .map(|&a| RationalNumber::new(a, (a.unsigned_abs() % 4) + 1))
+                    .collect::<Vec<_>>(),
+            );
+            assert_eq!(g.to_string().parse(), Ok(g));
+            let h = Polynomial::new(
+                &w.chunks(2)
+                    .map(|c| QuadInt::<0, 2>::new(c[0], c[1]))
+                    .collect::<Vec<_>>(),
+            );
+            assert_eq!(h.to_string().parse(), Ok(h));
+            let s =
+                PowerSeries::<ModInt<7>, 4>::new(&random_modints::<7>(6, w[0].unsigned_abs() + 1));
+            assert_eq!(s.to_string().parse(), Ok(s));
+        }
+        for seed in 1..20 {
+            let f = Polynomial::new(&random_modints::<MOD2>(seed as usize, seed));
+            assert_eq!(f.to_string().parse(), Ok(f));
+        }
+    }
+
+    /// 読めない文字列に対してエラーの位置と種類が正しく返ること
+    #[test]
+    fn parse_errors_test1() {
+        let e = ParseError::new;
+        assert_eq!(
+            "12a".parse::<Integer>().unwrap_err(),
+            e(2, ParseErrorKind::UnexpectedCharacter('a'))
+        );
+        assert_eq!(
+            "".parse::<Integer>().unwrap_err(),
+            e(0, ParseErrorKind::Empty)
+        );
+        assert_eq!(
+            "99999999999999999999".parse::<Integer>().unwrap_err(),
+            e(0, ParseErrorKind::InvalidNumber)
+        );
+        assert_eq!(
+            "3 / 0".parse::<RationalNumber>().unwrap_err(),
+            e(4, ParseErrorKind::ZeroDenominator)
+        );
+        assert_eq!(
+            "1.2.3".parse::<RealNumber>().unwrap_err(),
+            e(0, ParseErrorKind::InvalidNumber)
+        );
+        // NaN や無限大は Eq と両立しないので読まない
+        for s in ["NaN", "inf", "-infinity", "(1e400)"] {
+            assert_eq!(
+                s.parse::<RealNumber>().unwrap_err().kind(),
+                &ParseErrorKind::InvalidNumber
+            );
+        }
+        assert_eq!(
+            "x^2 + 3y".parse::<Polynomial<Integer>>().unwrap_err(),
+            e(7, ParseErrorKind::UnexpectedCharacter('y'))
+        );
+        assert_eq!(
+            "x^2 + * 1".parse::<Polynomial<Integer>>().unwrap_err(),
+            e(6, ParseErrorKind::UnexpectedCharacter('*'))
+        );
+        assert_eq!(
+            "x^2 + [1 + 1x"
+                .parse::<Polynomial<QuadInt<0, 2>>>()
+                .unwrap_err(),
+            e(6, ParseErrorKind::UnclosedDelimiter('['))
+        );
+        assert_eq!(
+            "x^2 + ".parse::<Polynomial<Integer>>().unwrap_err(),
+            e(6, ParseErrorKind::Empty)
+        );
+        assert_eq!(
+            "[1 + 2y]".parse::<QuadInt<0, 2>>().unwrap_err(),
+            e(6, ParseErrorKind::UnexpectedCharacter('y'))
+        );
+        assert_eq!(
+            "[1 + 2x] + 1"
+                .parse::<ModInt<7>>()
+                .map_err(|e| e.position()),
+            Err(0)
+        );
+        let err = "3 / 0".parse::<RationalNumber>().unwrap_err();
+        assert_eq!(err.to_string(), "zero denominator at position 4");
+        assert_eq!(err.kind(), &ParseErrorKind::ZeroDenominator);
+    }
+
+    /// 負の係数や-1の係数、```ModInt```係数の表示
+    #[test]
+    fn display_polynomial_test3() {
+        let n = Integer::new;
+        let f = Polynomial::new(&[n(3), n(-2), n(0), n(-1), n(1)]);
+        assert_eq!(f.to_string(), "3 - 2x - x^3 + x^4");
+        let g = Polynomial::new(&[n(-1), n(0), n(-5)]);
+        assert_eq!(g.to_string(), "-1 - 5x^2");
+        let h = Polynomial::new(&[ModInt::<7>::new(0), ModInt::new(3), ModInt::new(1)]);
+        assert_eq!(h.to_string(), "3x + x^2");
+        assert_eq!(Polynomial::<ModInt<7>>::zero().to_string(), "0");
+        let r = Polynomial::new(&[RationalNumber::new(-1, 2), RationalNumber::new(1, 3)]);
+        assert_eq!(r.to_string(), "-(1) / (2) + (1) / (3)x");
+        let z = Polynomial::new(&[Complex::new(n(1), n(0)), Complex::new(n(3), n(4))]);
+        assert_eq!(z.to_string(), "(1 + 0i) + (3 + 4i)x");
+        assert_eq!(z.to_string().parse(), Ok(z));
+    }
+
+    /// 表示の設定（変数名、順番、上付き文字、LaTeX）
+    #[test]
+    fn polynomial_format_test1() {
+        let n = Integer::new;
+        let f = Polynomial::new(&[
+            n(1),
+            n(-2),
+            n(0),
+            n(0),
+            n(0),
+            n(0),
+            n(0),
+            n(0),
+            n(0),
+            n(0),
+            n(0),
+            n(3),
+        ]);
+        let format = PolynomialFormat::new()
+            .variable("t")
+            .order(Order::Descending);
+        assert_eq!(format.polynomial(&f), "3t^11 - 2t + 1");
+        let unicode = format.clone().notation(Notation::Unicode);
+        assert_eq!(unicode.polynomial(&f), "3t¹¹ - 2t + 1");
+        let latex = PolynomialFormat::new().notation(Notation::Latex);
+        assert_eq!(latex.polynomial(&f), "1 - 2x + 3x^{11}");
+
+        let mut m = Matrix::new(2, 2);
+        m.change_entry(0, 0, f.clone());
+        m.change_entry(1, 1, Polynomial::x());
+        assert_eq!(
+            latex.matrix(&m),
+            "\\begin{pmatrix} 1 - 2x + 3x^{11} & 0 \\\\ 0 & x \\end{pmatrix}"
+        );
+        assert_eq!(format.matrix(&m), "[3t^11 - 2t + 1, 0]\n[0, t]");
+
+        let mut s = SolutionSet::new(HashSet::new());
+        assert_eq!(latex.solution_set(&s), "\\emptyset");
+        s.insert(Polynomial::new(&[n(0), n(-1)]));
+        assert_eq!(latex.solution_set(&s), "\\{ -x \\}");
+        assert_eq!(s.to_string(), "{-x}");
+    }
+
+    /// 有理数・剰余類・2次の拡大の元の LaTeX 表記
+    #[test]
+    fn to_latex_test1() {
+        let r = RationalNumber::new;
+        assert_eq!(r(-1, 2).to_latex(), "-\\frac{1}{2}");
+        assert_eq!(r(3, 1).to_latex(), "3");
+        assert_eq!(ModInt::<7>::new(3).to_latex(), "\\overline{3}");
+        assert_eq!(Integer::new(-4).to_latex(), "-4");
+        assert_eq!(QuadInt::<0, 2>::new(1, 2).to_latex(), "1 + 2\\sqrt{2}");
+        assert_eq!(QuadInt::<0, -1>::new(0, -1).to_latex(), "-i");
+        assert_eq!(QuadInt::<1, 1>::new(2, -1).to_latex(), "2 - \\alpha");
+        assert_eq!(
+            QuadField::<0, 3>::new(r(1, 2), r(1, 1)).to_latex(),
+            "\\frac{1}{2} + \\sqrt{3}"
+        );
+        assert_eq!(
+            Complex::new(r(1, 2), r(-3, 4)).to_latex(),
+            "\\frac{1}{2} - \\frac{3}{4}i"
+        );
+        let n = Integer::new;
+        assert_eq!(QuadExt::new(n(0), n(2), n(1), n(1)).to_latex(), "2\\alpha");
+    }
+
+    /// 多項式・行列・解集合の LaTeX 表記。入れ子になった型でも内側の表記を使う
+    #[test]
+    fn to_latex_test2() {
+        let r = RationalNumber::new;
+        let f = Polynomial::new(&[
+            Complex::new(r(1, 2), r(-3, 4)),
+            Complex::new(r(0, 1), r(1, 1)),
+            Complex::new(r(-1, 1), r(0, 1)),
+        ]);
+        assert_eq!(
+            f.to_latex(),
+            "\\left(\\frac{1}{2} - \\frac{3}{4}i\\right) + ix - x^{2}"
+        );
+        let mut m = Matrix::new(2, 2);
+        m.change_entry(0, 0, r(1, 2));
+        m.change_entry(1, 1, r(1, 1));
+        assert_eq!(
+            m.to_latex(),
+            "\\begin{pmatrix} \\frac{1}{2} & 0 \\\\ 0 & 1 \\end{pmatrix}"
+        );
+        let mut s = SolutionSet::new(HashSet::new());
+        s.insert(ModInt::<7>::new(3));
+        assert_eq!(s.to_latex(), "\\{ \\overline{3} \\}");
+    }
+
+    /// 行列の演算子と添字、転置
+    #[test]
+    fn matrix_operators_test1() {
+        let n = Integer::new;
+        let a = Matrix::from_rows(&[vec![n(1), n(2)], vec![n(3), n(4)]]);
+        let b = Matrix::from_rows(&[vec![n(0), n(1)], vec![n(1), n(0)]]);
+        assert_eq!(
+            a.clone() + b.clone(),
+            Matrix::from_rows(&[vec![n(1), n(3)], vec![n(4), n(4)]])
+        );
+        assert_eq!(
+            a.clone() - b.clone(),
+            Matrix::from_rows(&[vec![n(1), n(1)], vec![n(2), n(4)]])
+        );
+        assert_eq!(
+            a.clone() * b.clone(),
+            Matrix::from_rows(&[vec![n(2), n(1)], vec![n(4), n(3)]])
+        );
+        assert_eq!(
+            a.clone() * n(2),
+            Matrix::from_rows(&[vec![n(2), n(4)], vec![n(6), n(8)]])
+        );
+        assert_eq!(-a.clone() + a.clone(), Matrix::new(2, 2));
+        assert_eq!(b.pow(2), Matrix::identity(2));
+        assert_eq!(a[(1, 0)], n(3));
+        let mut c = a.clone();
+        c[(1, 0)] = n(5);
+        c *= Matrix::identity(2);
+        assert_eq!(c[(1, 0)], n(5));
+        assert_eq!(
+            a.transpose(),
+            Matrix::from_rows(&[vec![n(1), n(3)], vec![n(2), n(4)]])
+        );
+        assert_eq!(a.to_string(), "[1, 2]\n[3, 4]");
+    }
+
+    /// 型が合わないときのエラー
+    #[test]
+    fn matrix_operators_test2() {
+        let n = Integer::new;
+        let a = Matrix::from_rows(&[vec![n(1), n(2), n(3)]]);
+        let b = Matrix::<Integer>::identity(2);
+        let err = DimensionError {
+            left: (1, 3),
+            right: (2, 2),
+        };
+        assert_eq!(a.checked_add(&b), Err(err));
+        assert_eq!(a.checked_mul(&b), Err(err));
+        assert_eq!(err.to_string(), "dimension mismatch: 1x3 and 2x2");
+        assert!(a.checked_pow(2).is_err());
+        assert_eq!(
+            a.checked_mul(&a.transpose()),
+            Ok(Matrix::from_rows(&[vec![n(14)]]))
+        );
+    }
+
+    /// 型が合わない行列の積はpanicする
+    #[test]
+    #[should_panic]
+    fn matrix_operators_test3() {
+        let a = Matrix::<Integer>::new(2, 3);
+        let _ = a.clone() * a;
+    }
+
+    /// 有理数成分の行列の簡約階段形、階数、行列式、逆行列
+    #[test]
+    fn gaussian_elimination_test1() {
+        let r = |n: i64| RationalNumber::new(n, 1);
+        let a = Matrix::from_rows(&[
+            vec![r(2), r(1), r(-1)],
+            vec![r(-3), r(-1), r(2)],
+            vec![r(-2), r(1), r(2)],
+        ]);
+        assert_eq!(a.determinant(), r(-1));
+        assert_eq!(a.rank(), 3);
+        assert_eq!(a.rref(), Matrix::identity(3));
+        let b = a.clone().inverse().unwrap();
+        assert_eq!(a * b, Matrix::identity(3));
+
+        let c = Matrix::from_rows(&[
+            vec![r(1), r(2), r(3)],
+            vec![r(2), r(4), r(6)],
+            vec![r(1), r(0), r(1)],
+        ]);
+        assert_eq!(c.determinant(), r(0));
+        assert_eq!(c.rank(), 2);
+        assert_eq!(c.clone().inverse(), None);
+        assert_eq!(
+            c.rref(),
+            Matrix::from_rows(&[
+                vec![r(1), r(0), r(1)],
+                vec![r(0), r(1), r(1)],
+                vec![r(0), r(0), r(0)],
+            ])
+        );
+        assert_eq!(Matrix::<RationalNumber>::new(0, 0).determinant(), r(1));
+    }
+
+    /// F_7 での連立一次方程式。解全体が特殊解 + 核になっていることを確認
+    #[test]
+    fn gaussian_elimination_test2() {
+        let m = |n: u64| ModInt::<7>::new(n);
+        let a = Matrix::from_rows(&[vec![m(1), m(2), m(3), m(4)], vec![m(2), m(4), m(6), m(3)]]);
+        let b = vec![m(1), m(5)];
+        let (x, kernel) = a.solve(&b).unwrap();
+        assert_eq!(kernel.len(), 2);
+        let column =
+            |v: &[ModInt<7>]| Matrix::from_rows(&v.iter().map(|t| vec![*t]).collect::<Vec<_>>());
+        let b_column = column(&b);
+        assert_eq!(a.clone() * column(&x), b_column);
+        for (s, t) in [(1, 0), (0, 1), (3, 5)] {
+            let y: Vec<_> = (0..4)
+                .map(|i| x[i] + m(s) * kernel[0][i] + m(t) * kernel[1][i])
+                .collect();
+            assert_eq!(a.clone() * column(&y), b_column);
+        }
+        assert_eq!(a.kernel(), kernel);
+        // 2行目が1行目の2倍なのに右辺がそうなっていないので解なし
+        let a2 = Matrix::from_rows(&[vec![m(1), m(2)], vec![m(2), m(4)]]);
+        assert_eq!(a2.solve(&[m(1), m(3)]), None);
+    }
+
+    /// Q(√2) や Q(i) 成分でも使える
+    #[test]
+    fn gaussian_elimination_test3() {
+        let r = RationalNumber::new;
+        let q = |a: i64, b: i64| QuadField::<0, 2>::new(r(a, 1), r(b, 1));
+        let a = Matrix::from_rows(&[vec![q(1, 1), q(0, 1)], vec![q(0, 1), q(1, -1)]]);
+        // (1 + √2)(1 - √2) - √2 √2 = -1 - 2 = -3
+        assert_eq!(a.determinant(), q(-3, 0));
+        let z = |a: i64, b: i64| Complex::new(r(a, 1), r(b, 1));
+        let c = Matrix::from_rows(&[vec![z(1, 1), z(2, 0)], vec![z(0, 1), z(1, -1)]]);
+        let d = c.clone().inverse().unwrap();
+        assert_eq!(c * d, Matrix::identity(2));
+    }
+
+    /// 整数成分の行列の行列式と余因子行列。Bareiss、Berkowitz、掃き出し法（有理数）の結果が一致することも確認
+    #[test]
+    fn fraction_free_determinant_test1() {
+        let v = random_i64s(16 * 5, 31415, 9);
+        for (k, w) in v.chunks(16).enumerate() {
+            let size = k % 4 + 1;
+            let rows: Vec<Vec<i64>> = w
+                .chunks(4)
+                .take(size)
+                .map(|row| row[..size].to_vec())
+                .collect();
+            let a = Matrix::from_rows(
+                &rows
+                    .iter()
+                    .map(|row| row.iter().map(|&x| Integer::new(x)).collect())
+                    .collect::<Vec<_>>(),
+            );
+            let q = Matrix::from_rows(
+                &rows
+                    .iter()
+                    .map(|row| row.iter().map(|&x| RationalNumber::new(x, 1)).collect())
+                    .collect::<Vec<_>>(),
+            );
+            let det = a.determinant_bareiss();
+            assert_eq!(a.determinant_berkowitz(), det);
+            assert_eq!(q.determinant(), RationalNumber::new(det.to_int(), 1));
+            let adj = a.adjugate();
+            assert_eq!(a.clone() * adj.clone(), Matrix::identity(size) * det);
+            assert_eq!(adj * a, Matrix::identity(size) * det);
+        }
+        let singular = Matrix::from_rows(&[
+            vec![Integer::new(0), Integer::new(1)],
+            vec![Integer::new(0), Integer::new(2)],
+        ]);
+        assert_eq!(singular.determinant_bareiss(), Integer::new(0));
+    }
+
+    /// シルベスター行列の行列式で終結式を求める。(x - 1)(x - 2) と x - 3 の終結式は (1 - 3)(2 - 3) = 2
+    #[test]
+    fn fraction_free_determinant_test2() {
+        let n = Integer::new;
+        let s = Matrix::from_rows(&[
+            vec![n(1), n(-3), n(2)],
+            vec![n(1), n(-3), n(0)],
+            vec![n(0), n(1), n(-3)],
+        ]);
+        assert_eq!(s.determinant_bareiss(), n(2));
+        assert_eq!(s.determinant_berkowitz(), n(2));
+
+        // Z[i] 成分：det [[1 + i, 2], [i, 1 - i]] = (1 + i)(1 - i) - 2i = 2 - 2i
+        let g = QuadInt::<0, -1>::new;
+        let a = Matrix::from_rows(&[vec![g(1, 1), g(2, 0)], vec![g(0, 1), g(1, -1)]]);
+        assert_eq!(a.determinant_berkowitz(), g(2, -2));
+
+        // 多項式成分：det(xI - A) は A の特性多項式
+        let r = |a: i64| Polynomial::new(&[RationalNumber::new(a, 1)]);
+        let x = Polynomial::x();
+        let m = Matrix::from_rows(&[vec![x.clone() - r(1), -r(2)], vec![-r(3), x.clone() - r(4)]]);
+        let expected = Polynomial::new(&[
+            RationalNumber::new(-2, 1),
+            RationalNumber::new(-5, 1),
+            RationalNumber::new(1, 1),
+        ]);
+        assert_eq!(m.determinant_bareiss(), expected);
+        assert_eq!(m.determinant_berkowitz(), expected);
+    }
+
+    /// F_p と Q の行列で、2通りの特性多項式が一致し、ケイリー・ハミルトンの定理が成り立つことを確認
+    #[test]
+    fn characteristic_polynomial_test1() {
+        for (k, seed) in (1..8u64).enumerate() {
+            let size = k % 5 + 1;
+            let v = random_modints::<MOD2>(size * size, seed * 7919);
+            let a = Matrix::from_rows(&v.chunks(size).map(|row| row.to_vec()).collect::<Vec<_>>());
+            let p = a.characteristic_polynomial();
+            assert_eq!(p.deg(), size);
+            assert_eq!(p, a.characteristic_polynomial_hessenberg());
+            assert_eq!(Polynomial::evaluate_matrix(&p, &a), Matrix::new(size, size));
+        }
+        let w = random_i64s(16, 2718, 3);
+        let q = Matrix::from_rows(
+            &w.chunks(4)
+                .map(|row| row.iter().map(|&x| RationalNumber::new(x, 1)).collect())
+                .collect::<Vec<_>>(),
+        );
+        let p = q.characteristic_polynomial_hessenberg();
+        assert_eq!(p, q.characteristic_polynomial());
+        assert_eq!(Polynomial::evaluate_matrix(&p, &q), Matrix::new(4, 4));
+        // 定数項は (-1)^n det(A)
+        assert_eq!(p.coeff(0), q.determinant());
+        // ヘッセンベルグ化で列の入れ替えが必要な行列
+        let r = |x: i64| RationalNumber::new(x, 1);
+        let b = Matrix::from_rows(&[
+            vec![r(1), r(2), r(3)],
+            vec![r(0), r(4), r(5)],
+            vec![r(6), r(7), r(8)],
+        ]);
+        assert_eq!(
+            b.characteristic_polynomial_hessenberg(),
+            b.characteristic_polynomial()
+        );
+    }
+
+    /// 最小多項式。diag(2, 2, 3) の特性多項式は (x - 2)^2 (x - 3) だが、最小多項式は (x - 2)(x - 3)
+    #[test]
+    fn minimal_polynomial_test1() {
+        let r = |x: i64| RationalNumber::new(x, 1);
+        let mut a = Matrix::new(3, 3);
+        a[(0, 0)] = r(2);
+        a[(1, 1)] = r(2);
+        a[(2, 2)] = r(3);
+        assert_eq!(
+            a.characteristic_polynomial(),
+            Polynomial::from_roots(&[r(2), r(2), r(3)])
+        );
+        assert_eq!(
+            a.minimal_polynomial(),
+            Polynomial::from_roots(&[r(2), r(3)])
+        );
+        // ジョルダン細胞 J_2(2) は最小多項式も (x - 2)^2
+        a[(0, 1)] = r(1);
+        assert_eq!(
+            a.minimal_polynomial(),
+            Polynomial::from_roots(&[r(2), r(2), r(3)])
+        );
+        // フィボナッチ数列の漸化式の行列
+        let m = |x: u64| ModInt::<MOD1>::new(x);
+        let f = Matrix::from_rows(&[vec![m(1), m(1)], vec![m(1), m(0)]]);
+        let p = Polynomial::new(&[-m(1), -m(1), m(1)]);
+        assert_eq!(f.minimal_polynomial(), p);
+        assert_eq!(f.characteristic_polynomial(), p);
+        assert_eq!(
+            Matrix::<ModInt<MOD1>>::identity(4).minimal_polynomial(),
+            Polynomial::new(&[-m(1), m(1)])
+        );
+        // 最小多項式は特性多項式を割り切る
+        let v = random_modints::<7>(16, 99);
+        let b = Matrix::from_rows(&v.chunks(4).map(|row| row.to_vec()).collect::<Vec<_>>());
+        let b = b.clone() * b.clone() - Matrix::identity(4);
+        let minimal = b.minimal_polynomial();
+        assert_eq!(
+            b.characteristic_polynomial() % minimal.clone(),
+            Polynomial::zero()
+        );
+        assert_eq!(Polynomial::evaluate_matrix(&minimal, &b), Matrix::new(4, 4));
+    }
+
+    /// 整数行列を成分ごとに作るための補助
+    fn integer_matrix(rows: &[&[i64]]) -> Matrix<Integer> {
+        Matrix::from_rows(
+            &rows
+                .iter()
+                .map(|row| row.iter().map(|&x| Integer::new(x)).collect())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// 整数行列のエルミート標準形
+    #[test]
+    fn hermite_normal_form_test1() {
+        let a = integer_matrix(&[&[2, 3, 6, 2], &[5, 6, 1, 6], &[8, 3, 1, 1]]);
+        let (h, u) = a.hermite_normal_form();
+        assert_eq!(
+            h,
+            integer_matrix(&[&[1, 0, 50, -11], &[0, 3, 28, -2], &[0, 0, 61, -13]])
+        );
+        assert_eq!(u.clone() * a, h);
+        assert_eq!(u.determinant_bareiss().norm(), 1);
+
+        // 階数落ちしている場合
+        let b = integer_matrix(&[&[2, 4], &[3, 6], &[-4, -8]]);
+        let (h, u) = b.hermite_normal_form();
+        assert_eq!(h, integer_matrix(&[&[1, 2], &[0, 0], &[0, 0]]));
+        assert_eq!(u * b, h);
+    }
+
+    /// スミス標準形と単因子、整数解の基底
+    #[test]
+    fn smith_normal_form_test1() {
+        let a = integer_matrix(&[&[2, 4, 4], &[-6, 6, 12], &[10, -4, -16]]);
+        let (d, u, v) = a.smith_normal_form();
+        assert_eq!(d, integer_matrix(&[&[2, 0, 0], &[0, 6, 0], &[0, 0, 12]]));
+        assert_eq!(u.clone() * a.clone() * v.clone(), d);
+        assert_eq!(u.determinant_bareiss().norm(), 1);
+        assert_eq!(v.determinant_bareiss().norm(), 1);
+
+        // Z^2 / <(2, 0), (0, 3)> は Z/6 と同型
+        let b = integer_matrix(&[&[2, 0], &[0, 3]]);
+        assert_eq!(
+            b.invariant_factors(),
+            vec![Integer::new(1), Integer::new(6)]
+        );
+        // Z^3 / <(2, 4, 6)> は Z/2 × Z^2
+        let c = integer_matrix(&[&[2, 4, 6]]);
+        assert_eq!(c.invariant_factors(), vec![Integer::new(2)]);
+
+        // x + 2y + 3z = 0 の整数解は2次元の格子
+        let kernel = integer_matrix(&[&[1, 2, 3]]).module_kernel();
+        assert_eq!(kernel.len(), 2);
+        for x in &kernel {
+            assert_eq!(
+                x[0] + Integer::new(2) * x[1] + Integer::new(3) * x[2],
+                Integer::new(0)
+            );
+        }
+        let k = Matrix::from_rows(&kernel);
+        assert_eq!(
+            k.invariant_factors(),
+            vec![Integer::new(1), Integer::new(1)]
+        );
+    }
+
+    /// F_7[x] 成分の xI - A のスミス標準形。最後の単因子が最小多項式、積が特性多項式になる
+    #[test]
+    fn smith_normal_form_test2() {
+        let m = |n: u64| ModInt::<7>::new(n);
+        let mut a = Matrix::new(3, 3);
+        a[(0, 0)] = m(2);
+        a[(1, 1)] = m(2);
+        a[(2, 2)] = m(3);
+        a[(0, 2)] = m(5);
+        let mut b = Matrix::new(3, 3);
+        for i in 0..3 {
+            for j in 0..3 {
+                b[(i, j)] = Polynomial::new_constant(-a[(i, j)]);
+            }
+            b[(i, i)] += Polynomial::x();
+        }
+        let factors = b.invariant_factors();
+        assert_eq!(factors.len(), 3);
+        assert_eq!(factors[0], Polynomial::identity());
+        assert_eq!(factors[2], a.minimal_polynomial());
+        assert_eq!(
+            factors
+                .iter()
+                .fold(Polynomial::identity(), |acc, f| acc * f.clone()),
+            a.characteristic_polynomial()
+        );
+    }
+
+    fn rational_matrix(rows: &[&[i64]]) -> Matrix<RationalNumber> {
+        Matrix::from_rows(
+            &rows
+                .iter()
+                .map(|row| row.iter().map(|&x| RationalNumber::new(x, 1)).collect())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// 有理数係数の多項式の有理数の根
+    #[test]
+    fn rational_roots_test1() {
+        let r = |n: i64, d: u64| RationalNumber::new(n, d);
+        let f = Polynomial::from_roots(&[r(1, 2), r(1, 2), r(-3, 1)])
+            * Polynomial::new(&[r(1, 1), r(0, 1), r(1, 1)]);
+        let s = rational_roots(&f);
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.multiplicity(&r(1, 2)), 2);
+        assert_eq!(s.multiplicity(&r(-3, 1)), 1);
+
+        // x^2 (2x - 3) / 5
+        let g = Polynomial::new(&[r(0, 1), r(0, 1), r(-3, 5), r(2, 5)]);
+        let s = rational_roots(&g);
+        assert_eq!(s.multiplicity(&r(0, 1)), 2);
+        assert_eq!(s.multiplicity(&r(3, 2)), 1);
+        assert_eq!(s.len(), 2);
+    }
+
+    /// 分母を払うと係数がi64に収まらないときは、桁あふれせずにpanicする
+    #[test]
+    #[should_panic(expected = "coefficients are too large to clear denominators")]
+    fn rational_roots_test2() {
+        let f = Polynomial::new(&[
+            RationalNumber::new(-(1 << 62), 1),
+            RationalNumber::new(1, 3),
+        ]);
+        rational_roots(&f);
+    }
+
+    /// フロベニウス標準形。P^{-1}AP = F となり、最後のブロックが最小多項式の同伴行列になる
+    #[test]
+    fn frobenius_normal_form_test1() {
+        let a = rational_matrix(&[&[2, 0, 0], &[0, 2, 0], &[0, 0, 3]]);
+        let (f, p) = a.frobenius_normal_form();
+        assert_eq!(f, rational_matrix(&[&[2, 0, 0], &[0, 0, -6], &[0, 1, 5]]));
+        assert_eq!(p.clone().inverse().unwrap() * a * p, f);
+
+        let v = random_i64s(16, 11, 4);
+        let mut b = Matrix::new(4, 4);
+        for i in 0..4 {
+            for j in 0..4 {
+                b[(i, j)] = ModInt::<5>::new(v[4 * i + j].rem_euclid(5) as u64);
+            }
+        }
+        // 固有値2の2次元の固有空間を持つように作り直す
+        let c = Matrix::block_diagonal(&[
+            Matrix::jordan_block(ModInt::<5>::new(2), 1),
+            Matrix::jordan_block(ModInt::<5>::new(2), 2),
+            Matrix::jordan_block(ModInt::<5>::new(4), 1),
+        ]);
+        let b = match b.clone().inverse() {
+            Some(inv) => inv * c * b,
+            None => c,
+        };
+        let (f, p) = b.frobenius_normal_form();
+        assert_eq!(p.clone().inverse().unwrap() * b.clone() * p, f);
+        let minimal = b.minimal_polynomial();
+        assert_eq!(minimal.deg(), 3);
+        assert_eq!(
+            f,
+            Matrix::block_diagonal(&[
+                Matrix::companion(&Polynomial::from_roots(&[ModInt::<5>::new(2)])),
+                Matrix::companion(&minimal),
+            ])
+        );
+    }
+
+    /// 相似の判定と相似変換
+    #[test]
+    fn similarity_test1() {
+        let a = rational_matrix(&[&[1, 2, 0], &[0, 1, 0], &[3, -1, 2]]);
+        let s = rational_matrix(&[&[1, 2, 0], &[1, 3, 1], &[0, 1, 2]]);
+        let b = s.clone().inverse().unwrap() * a.clone() * s;
+        assert!(a.is_similar(&b));
+        let q = a.similarity_transform(&b).unwrap();
+        assert_eq!(q.clone().inverse().unwrap() * a.clone() * q, b);
+
+        // 特性多項式は同じでも最小多項式が違えば相似でない
+        let c = rational_matrix(&[&[1, 0], &[0, 1]]);
+        let d = rational_matrix(&[&[1, 1], &[0, 1]]);
+        assert!(!c.is_similar(&d));
+        assert_eq!(c.similarity_transform(&d), None);
+        assert!(!a.is_similar(&c));
+    }
+
+    /// 有理数成分の行列のジョルダン標準形
+    #[test]
+    fn jordan_normal_form_test1() {
+        let a = rational_matrix(&[
+            &[5, 4, 2, 1],
+            &[0, 1, -1, -1],
+            &[-1, -1, 3, 0],
+            &[1, 1, -1, 2],
+        ]);
+        let (j, p) = a.jordan_normal_form().unwrap();
+        let r = |n: i64| RationalNumber::new(n, 1);
+        assert_eq!(
+            j,
+            Matrix::block_diagonal(&[
+                Matrix::jordan_block(r(1), 1),
+                Matrix::jordan_block(r(2), 1),
+                Matrix::jordan_block(r(4), 2),
+            ])
+        );
+        assert_eq!(p.clone().inverse().unwrap() * a * p, j);
+
+        // 冪零行列
+        let n = rational_matrix(&[&[0, 1, 0, 0], &[0, 0, 0, 0], &[0, 0, 0, 1], &[0, 0, 0, 0]]);
+        let (j, p) = n.jordan_normal_form().unwrap();
+        assert_eq!(j, n);
+        assert_eq!(p.clone().inverse().unwrap() * n * p, j);
+    }
+
+    /// F_p 成分の行列のジョルダン標準形と、2次体に広げたときのジョルダン標準形
+    #[test]
+    fn jordan_normal_form_test2() {
+        // x^2 + 1 は F_7 では既約、F_5 では (x - 2)(x - 3)
+        let a = Matrix::companion(&Polynomial::new(&[
+            ModInt::<7>::new(1),
+            ModInt::<7>::new(0),
+            ModInt::<7>::new(1),
+        ]));
+        assert_eq!(a.jordan_normal_form(), None);
+        let b = Matrix::companion(&Polynomial::new(&[
+            ModInt::<5>::new(1),
+            ModInt::<5>::new(0),
+            ModInt::<5>::new(1),
+        ]));
+        let (j, p) = b.jordan_normal_form().unwrap();
+        assert_eq!(
+            j,
+            Matrix::from_rows(&[
+                vec![ModInt::<5>::new(2), ModInt::<5>::new(0)],
+                vec![ModInt::<5>::new(0), ModInt::<5>::new(3)],
+            ])
+        );
+        assert_eq!(p.clone().inverse().unwrap() * b * p, j);
+
+        // x^2 - 2 は Q では分解しないが Q(√2) では分解する
+        let c = rational_matrix(&[&[1, 2], &[1, 1]]);
+        assert_eq!(c.jordan_normal_form(), None);
+        let zero = RationalNumber::new(0, 1);
+        let one = RationalNumber::new(1, 1);
+        let d = c.map(|&x| QuadField::<0, 2>::new(x, zero));
+        let eigenvalues = [
+            QuadField::<0, 2>::new(one, one),
+            QuadField::<0, 2>::new(one, -one),
+        ];
+        let (j, p) = d.jordan_normal_form_with(&eigenvalues).unwrap();
+        let mut diagonal = Matrix::new(2, 2);
+        diagonal[(0, 0)] = eigenvalues[0];
+        diagonal[(1, 1)] = eigenvalues[1];
+        assert_eq!(j, diagonal);
+        assert_eq!(p.clone().inverse().unwrap() * d.clone() * p, j);
+        // 固有値でないものしか渡さなければ None
+        assert_eq!(d.jordan_normal_form_with(&[QuadField::identity()]), None);
+    }
+
+    /// Roots を実装していれば DynModInt 成分の行列でもジョルダン標準形を求められる。根は決まった順に並ぶ
+    #[test]
+    fn jordan_normal_form_test3() {
+        DynModInt::set_modulus(5);
+        let m = |n: u64| DynModInt::new(n);
+        // (x - 3)(x - 2)
+        let f = Polynomial::new(&[m(1), m(0), m(1)]);
+        assert_eq!(DynModInt::roots(&f), Some(vec![m(2), m(3)]));
+        assert_eq!(DynModInt::roots(&Polynomial::new(&[m(0)])), None);
+        let b = Matrix::companion(&f);
+        let (j, p) = b.jordan_normal_form().unwrap();
+        assert_eq!(j, Matrix::from_rows(&[vec![m(2), m(0)], vec![m(0), m(3)]]));
+        assert_eq!(p.clone().inverse().unwrap() * b * p, j);
+
+        let r = |n: i64| RationalNumber::new(n, 1);
+        let g = Polynomial::from_roots(&[r(3), r(-5), r(3)]);
+        assert_eq!(RationalNumber::roots(&g), Some(vec![r(-5), r(3)]));
+    }
+
+    /// 定数項が大きくても、約数を素因数分解から作るのですぐ終わる
+    #[test]
+    fn rational_roots_test3() {
+        let r = |n: i64| RationalNumber::new(n, 1);
+        // (x - 999999937)(x + 3)
+        let f = Polynomial::from_roots(&[r(999_999_937), r(-3)]);
+        let s = rational_roots(&f);
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.multiplicity(&r(999_999_937)), 1);
+        assert_eq!(s.multiplicity(&r(-3)), 1);
+    }
+
+    fn real_matrix(rows: &[&[f64]]) -> Matrix<RealNumber> {
+        Matrix::from_rows(
+            &rows
+                .iter()
+                .map(|row| row.iter().map(|&x| RealNumber::new(x)).collect())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn complex_matrix(rows: &[&[(f64, f64)]]) -> Matrix<Complex<RealNumber>> {
+        Matrix::from_rows(
+            &rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&(a, b)| Complex::new(RealNumber::new(a), RealNumber::new(b)))
+                        .collect()
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// 部分ピボット選択付きLU分解と、それを使った連立一次方程式の解
+    #[test]
+    fn lu_test1() {
+        let a = real_matrix(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], &[7.0, 8.0, 10.0]]);
+        let (p, l, u) = a.lu();
+        assert!((p.clone() * a.clone()).approx_eq(&(l.clone() * u.clone()), 1e-12));
+        for i in 0..3 {
+            assert_eq!(l[(i, i)], RealNumber::new(1.0));
+            for j in i + 1..3 {
+                assert_eq!(l[(i, j)], RealNumber::new(0.0));
+                assert_eq!(u[(j, i)], RealNumber::new(0.0));
+            }
+        }
+        // 最初の主成分は絶対値最大の7
+        assert_eq!(u[(0, 0)], RealNumber::new(7.0));
+
+        let b = [
+            RealNumber::new(6.0),
+            RealNumber::new(15.0),
+            RealNumber::new(25.0),
+        ];
+        let x = a.lu_solve(&b).unwrap();
+        for (y, z) in x.iter().zip(&[1.0, 1.0, 1.0]) {
+            assert!(y.approx_eq(&RealNumber::new(*z), 1e-12));
+        }
+
+        // 正則でない行列
+        let singular = real_matrix(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], &[7.0, 8.0, 9.0]]);
+        assert_eq!(singular.lu_solve(&b), None);
+        assert_eq!(singular.condition_number(), f64::INFINITY);
+
+        // 複素数
+        let c = complex_matrix(&[&[(1.0, 1.0), (2.0, 0.0)], &[(0.0, -1.0), (3.0, 2.0)]]);
+        let (p, l, u) = c.lu();
+        assert!((p * c.clone()).approx_eq(&(l * u), 1e-12));
+        let w = [
+            Complex::new(RealNumber::new(1.0), RealNumber::new(0.0)),
+            Complex::new(RealNumber::new(0.0), RealNumber::new(1.0)),
+        ];
+        let z = c.lu_solve(&w).unwrap();
+        let cz = c * Matrix::from_rows(&[vec![z[0]], vec![z[1]]]);
+        assert!(cz.approx_eq(&Matrix::from_rows(&[vec![w[0]], vec![w[1]]]), 1e-12));
+    }
+
+    /// 条件数。ヒルベルト行列は条件が悪い
+    #[test]
+    fn condition_number_test1() {
+        let identity = Matrix::<RealNumber>::identity(3);
+        assert!((identity.condition_number() - 1.0).abs() < 1e-12);
+        let d = real_matrix(&[&[1.0, 0.0], &[0.0, 1e-3]]);
+        assert!((d.condition_number() - 1e3).abs() < 1e-9);
+
+        let mut h = Matrix::new(4, 4);
+        for i in 0..4 {
+            for j in 0..4 {
+                h[(i, j)] = RealNumber::new(1.0 / (i + j + 1) as f64);
+            }
+        }
+        assert!((h.condition_number() - 28375.0).abs() < 1e-6);
+        assert!((h.norm_one() - 25.0 / 12.0).abs() < 1e-12);
+    }
+
+    /// ハウスホルダー変換によるQR分解
+    #[test]
+    fn qr_test1() {
+        let v = random_i64s(12, 5, 9);
+        let mut a = Matrix::new(4, 3);
+        for i in 0..4 {
+            for j in 0..3 {
+                a[(i, j)] = RealNumber::new(v[3 * i + j] as f64);
+            }
+        }
+        let (q, r) = a.qr();
+        assert!((q.conjugate_transpose() * q.clone()).approx_eq(&Matrix::identity(4), 1e-12));
+        assert!((q * r.clone()).approx_eq(&a, 1e-12));
+        for i in 0..4 {
+            for j in 0..i.min(3) {
+                assert_eq!(r[(i, j)], RealNumber::new(0.0));
+            }
+        }
+
+        let c = complex_matrix(&[
+            &[(1.0, 2.0), (0.0, 1.0), (3.0, 0.0)],
+            &[(2.0, -1.0), (1.0, 1.0), (0.0, 0.0)],
+            &[(0.0, 0.0), (4.0, 0.0), (1.0, -3.0)],
+        ]);
+        let (q, r) = c.qr();
+        assert!((q.conjugate_transpose() * q.clone()).approx_eq(&Matrix::identity(3), 1e-12));
+        assert!((q * r).approx_eq(&c, 1e-12));
+    }
+
+    /// 最小二乗法による直線の当てはめ
+    #[test]
+    fn least_squares_test1() {
+        // y = 2x + 1 の上の点なら厳密に求まる
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let a = Matrix::from_rows(
+            &xs.iter()
+                .map(|&x| vec![RealNumber::new(1.0), RealNumber::new(x)])
+                .collect::<Vec<_>>(),
+        );
+        let b: Vec<RealNumber> = xs.iter().map(|&x| RealNumber::new(2.0 * x + 1.0)).collect();
+        let c = a.least_squares(&b).unwrap();
+        assert!(c[0].approx_eq(&RealNumber::new(1.0), 1e-12));
+        assert!(c[1].approx_eq(&RealNumber::new(2.0), 1e-12));
+
+        // (0, 1), (1, 0), (2, 2) に当てはめると y = x/2 + 1/2
+        let a = real_matrix(&[&[1.0, 0.0], &[1.0, 1.0], &[1.0, 2.0]]);
+        let b = [
+            RealNumber::new(1.0),
+            RealNumber::new(0.0),
+            RealNumber::new(2.0),
+        ];
+        let c = a.least_squares(&b).unwrap();
+        assert!(c[0].approx_eq(&RealNumber::new(0.5), 1e-12));
+        assert!(c[1].approx_eq(&RealNumber::new(0.5), 1e-12));
+
+        // 列が一次従属
+        let d = real_matrix(&[&[1.0, 2.0], &[2.0, 4.0], &[3.0, 6.0]]);
+        assert_eq!(d.least_squares(&b), None);
+    }
+
+    /// コレスキー分解
+    #[test]
+    fn cholesky_test1() {
+        let a = real_matrix(&[
+            &[4.0, 12.0, -16.0],
+            &[12.0, 37.0, -43.0],
+            &[-16.0, -43.0, 98.0],
+        ]);
+        let l = a.cholesky().unwrap();
+        assert!(l.approx_eq(
+            &real_matrix(&[&[2.0, 0.0, 0.0], &[6.0, 1.0, 0.0], &[-8.0, 5.0, 3.0]]),
+            1e-12
+        ));
+        assert!((l.clone() * l.conjugate_transpose()).approx_eq(&a, 1e-12));
+
+        // 正定値でない
+        let b = real_matrix(&[&[1.0, 2.0], &[2.0, 1.0]]);
+        assert_eq!(b.cholesky(), None);
+
+        // エルミート行列
+        let c = complex_matrix(&[&[(2.0, 0.0), (0.0, -1.0)], &[(0.0, 1.0), (2.0, 0.0)]]);
+        let l = c.cholesky().unwrap();
+        assert!((l.clone() * l.conjugate_transpose()).approx_eq(&c, 1e-12));
+    }
+
+    /// 対称行列の固有値分解
+    #[test]
+    fn symmetric_eigendecomposition_test1() {
+        let a = real_matrix(&[&[2.0, 1.0, 0.0], &[1.0, 2.0, 1.0], &[0.0, 1.0, 2.0]]);
+        let (values, v) = a.symmetric_eigendecomposition();
+        let expected = [2.0 - 2f64.sqrt(), 2.0, 2.0 + 2f64.sqrt()];
+        for (x, y) in values.iter().zip(&expected) {
+            assert!(x.approx_eq(&RealNumber::new(*y), 1e-12));
+        }
+        assert!((v.transpose() * v.clone()).approx_eq(&Matrix::identity(3), 1e-12));
+        let mut d = Matrix::new(3, 3);
+        for i in 0..3 {
+            d[(i, i)] = values[i];
+        }
+        assert!((v.clone() * d * v.transpose()).approx_eq(&a, 1e-12));
+
+        // 乱数で作った対称行列
+        let w = random_i64s(25, 3, 9);
+        let mut b = Matrix::new(5, 5);
+        for i in 0..5 {
+            for j in 0..=i {
+                b[(i, j)] = RealNumber::new(w[5 * i + j] as f64);
+                b[(j, i)] = b[(i, j)];
+            }
+        }
+        let (values, v) = b.symmetric_eigendecomposition();
+        assert!(values.windows(2).all(|w| w[0].to_f64() <= w[1].to_f64()));
+        for (i, lambda) in values.iter().enumerate() {
+            let x = Matrix::from_rows(&(0..5).map(|k| vec![v[(k, i)]]).collect::<Vec<_>>());
+            assert!((b.clone() * x.clone()).approx_eq(&(x * *lambda), 1e-10));
+        }
+    }
+
+    /// 一般の実行列の固有値。実数でない固有値は複素数で返る
+    #[test]
+    fn eigenvalues_test1() {
+        let c = |re: f64, im: f64| Complex::new(RealNumber::new(re), RealNumber::new(im));
+        // (x - 1)(x - 2)(x^2 + 1) の同伴行列
+        let f = Polynomial::new(
+            &[2.0, -3.0, 3.0, -3.0, 1.0]
+                .iter()
+                .map(|&x| RealNumber::new(x))
+                .collect::<Vec<_>>(),
+        );
+        let values = Matrix::companion(&f).eigenvalues().unwrap();
+        let expected = [c(0.0, -1.0), c(0.0, 1.0), c(1.0, 0.0), c(2.0, 0.0)];
+        assert_eq!(values.len(), 4);
+        for (x, y) in values.iter().zip(&expected) {
+            assert!(x.approx_eq(y, 1e-9));
+        }
+
+        // 固有値の和はトレース、積は行列式
+        let w = random_i64s(36, 7, 5);
+        let mut a = Matrix::new(6, 6);
+        for i in 0..6 {
+            for j in 0..6 {
+                a[(i, j)] = RealNumber::new(w[6 * i + j] as f64);
+            }
+        }
+        let values = a.eigenvalues().unwrap();
+        assert_eq!(values.len(), 6);
+        let trace = (0..6).fold(0.0, |acc, i| acc + a[(i, i)].to_f64());
+        let sum = values.iter().fold(c(0.0, 0.0), |acc, &x| acc + x);
+        assert!(sum.approx_eq(&c(trace, 0.0), 1e-9));
+        let product = values.iter().fold(c(1.0, 0.0), |acc, &x| acc * x);
+        let det = a.determinant().to_f64();
+        assert!(product.approx_eq(&c(det, 0.0), 1e-9 * det.abs().max(1.0)));
+
+        let b = real_matrix(&[&[0.0, -1.0], &[1.0, 0.0]]);
+        let values = b.eigenvalues().unwrap();
+        assert!(values[0].approx_eq(&c(0.0, -1.0), 1e-12));
+        assert!(values[1].approx_eq(&c(0.0, 1.0), 1e-12));
+    }
+
+    /// 特異値分解
+    #[test]
+    fn singular_value_decomposition_test1() {
+        let a = real_matrix(&[&[3.0, 0.0], &[4.0, 5.0]]);
+        let sigma = a.singular_values();
+        assert!(sigma[0].approx_eq(&RealNumber::new(45f64.sqrt()), 1e-12));
+        assert!(sigma[1].approx_eq(&RealNumber::new(5f64.sqrt()), 1e-12));
+
+        let check = |a: &Matrix<RealNumber>| {
+            let (u, sigma, v) = a.singular_value_decomposition();
+            let k = a.rows().min(a.columns());
+            assert_eq!((u.rows(), u.columns()), (a.rows(), k));
+            assert_eq!((v.rows(), v.columns()), (a.columns(), k));
+            assert!((u.transpose() * u.clone()).approx_eq(&Matrix::identity(k), 1e-12));
+            assert!((v.transpose() * v.clone()).approx_eq(&Matrix::identity(k), 1e-12));
+            assert!(sigma.windows(2).all(|w| w[0].to_f64() >= w[1].to_f64()));
+            let mut d = Matrix::new(k, k);
+            for i in 0..k {
+                d[(i, i)] = sigma[i];
+            }
+            assert!((u * d * v.transpose()).approx_eq(a, 1e-10));
+        };
+        let w = random_i64s(12, 13, 9);
+        let mut b = Matrix::new(4, 3);
+        for i in 0..4 {
+            for j in 0..3 {
+                b[(i, j)] = RealNumber::new(w[3 * i + j] as f64);
+            }
+        }
+        check(&b);
+        check(&b.transpose());
+
+        // 階数落ちしている場合
+        let c = real_matrix(&[&[1.0, 2.0], &[2.0, 4.0], &[3.0, 6.0]]);
+        check(&c);
+        let sigma = c.singular_values();
+        assert!(sigma[0].approx_eq(&RealNumber::new(70f64.sqrt()), 1e-12));
+        assert_eq!(sigma[1], RealNumber::new(0.0));
+    }
 }
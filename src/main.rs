@@ -1,27 +1,41 @@
+pub mod algebraic_structures;
 pub mod characteristic;
+pub mod complexification;
+pub mod convolution;
+pub mod dyn_modint;
 pub mod f_p;
+pub mod format;
 pub mod identities;
 pub mod integer;
 pub mod inverse;
+pub mod latex;
 pub mod matrix;
 pub mod modint;
+pub mod number_theory;
+pub mod parse;
 pub mod polynomial;
+pub mod power_series;
 pub mod quadratic_extension;
 pub mod quadratic_field;
 pub mod quadratic_integer;
 pub mod rational_number;
 pub mod real_number;
+pub mod roots;
+pub mod scalar;
 pub mod solution_set;
+pub mod square_root;
 
 use crate::integer::Integer;
 use crate::modint::ModInt;
 use crate::polynomial::Polynomial;
 use crate::solution_set::SolutionSet;
 
-use crate::f_p::{solve_equation, MOD};
+use crate::f_p::solve_equation;
 
 use std::collections::HashSet;
 
+const MOD: u64 = 103; // テキトー
+
 /// 解集合に実装したDisplayトレイトの振る舞いを確認するその1
 fn display_solution_set_test1() {
     let mut s = SolutionSet::new(HashSet::<Integer>::new());
@@ -112,7 +126,7 @@ fn main() {
     derivative_of_polynomial1();
     derivative_of_polynomial2();
 
-    // F_103での方程式 x^3+3x^2+95x+4 = 0 の解を全探索
+    // F_103での方程式 x^3+3x^2+95x+4 = 0 の解を求める
     let v: Vec<ModInt<MOD>> = vec![
         ModInt::<MOD>::new(4),
         ModInt::<MOD>::new(95),
@@ -120,9 +134,11 @@ fn main() {
         ModInt::<MOD>::new(1),
     ];
     let f1 = Polynomial::new(&v);
-    let s = solve_equation(f1.clone());
-    println!(
-        "The solution set of the equation {} = 0 in F_{} is {}",
-        f1, MOD, s
-    );
+    match solve_equation(f1.clone()) {
+        Some(s) => println!(
+            "The solution set of the equation {} = 0 in F_{} is {}",
+            f1, MOD, s
+        ),
+        None => println!("Every element of F_{} is a solution of {} = 0", MOD, f1),
+    }
 }
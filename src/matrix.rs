@@ -1,16 +1,27 @@
-use crate::identities::{Identity, Zero};
-use std::ops::{Add, AddAssign, Mul};
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, EuclideanDomain, Field, Ring};
+use crate::complexification::Complex;
+use crate::format::Notation;
+use crate::identities::Identity;
+use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::polynomial::Polynomial;
+use crate::real_number::RealNumber;
+use crate::roots::Roots;
+use crate::scalar::Scalar;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
 
 /// （正方形とは限らない）行列
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Matrix<T> {
     entries: Vec<Vec<T>>,
     rows: usize,
     columns: usize,
 }
 
-/// 関連関数群。零元と単位元、加算と乗算を持っていることを仮定。
-impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Identity> Matrix<T> {
+/// 関連関数群。成分が環の元であることを仮定。
+impl<T: Ring> Matrix<T> {
     pub fn new(r: usize, c: usize) -> Matrix<T> {
         Matrix {
             entries: vec![vec![T::zero(); c]; r],
@@ -21,8 +32,8 @@ impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Identit
 
     pub fn new_id(zero: T, one: T, n: usize) -> Matrix<T> {
         let mut v = vec![vec![zero; n]; n];
-        for i in 0..n {
-            v[i][i] = one;
+        for (i, row) in v.iter_mut().enumerate() {
+            row[i] = one.clone();
         }
         Matrix {
             entries: v,
@@ -31,6 +42,195 @@ impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Identit
         }
     }
 
+    /// 行数
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// 列数
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// 正方行列かどうか
+    pub fn is_square(&self) -> bool {
+        self.rows == self.columns
+    }
+
+    /// 行を並べた配列から作る。行の長さが揃っていなければpanicする。
+    pub fn from_rows(v: &[Vec<T>]) -> Matrix<T> {
+        let columns = v.first().map_or(0, |row| row.len());
+        assert!(
+            v.iter().all(|row| row.len() == columns),
+            "rows of a matrix must have the same length"
+        );
+        Matrix {
+            entries: v.to_vec(),
+            rows: v.len(),
+            columns,
+        }
+    }
+
+    /// n次の単位行列。```new_id()```と違って零元と単位元を渡さなくてよい。
+    pub fn identity(n: usize) -> Matrix<T> {
+        Matrix::new_id(T::zero(), T::identity(), n)
+    }
+
+    /// 転置行列
+    pub fn transpose(&self) -> Matrix<T> {
+        let mut v = vec![Vec::with_capacity(self.rows); self.columns];
+        for row in &self.entries {
+            for (j, entry) in row.iter().enumerate() {
+                v[j].push(entry.clone());
+            }
+        }
+        Matrix {
+            entries: v,
+            rows: self.columns,
+            columns: self.rows,
+        }
+    }
+
+    /// 同伴行列。monicな多項式 f = x^k + c_{k-1}x^{k-1} + ... + c_0 に対し、
+    /// 対角の1つ下が1、最後の列が -c_0, -c_1, ..., -c_{k-1} で、他は0の k×k 行列。特性多項式と最小多項式が f になる。
+    /// fがmonicでなくても最高次係数は見ない。
+    pub fn companion(f: &Polynomial<T>) -> Matrix<T> {
+        let k = f.deg();
+        let mut a = Matrix::new(k, k);
+        for i in 0..k {
+            if i + 1 < k {
+                a.entries[i + 1][i] = T::identity();
+            }
+            a.entries[i][k - 1] = -f.coeff(i);
+        }
+        a
+    }
+
+    /// 固有値 λ の k 次のジョルダン細胞。対角成分が λ、対角の1つ上が1で、他は0。
+    pub fn jordan_block(lambda: T, k: usize) -> Matrix<T> {
+        let mut a = Matrix::new(k, k);
+        for i in 0..k {
+            a.entries[i][i] = lambda.clone();
+            if i + 1 < k {
+                a.entries[i][i + 1] = T::identity();
+            }
+        }
+        a
+    }
+
+    /// 行列を順に対角に並べたブロック対角行列。各行列は正方でなくてもよい。
+    pub fn block_diagonal(blocks: &[Matrix<T>]) -> Matrix<T> {
+        let rows = blocks.iter().map(|b| b.rows).sum();
+        let columns = blocks.iter().map(|b| b.columns).sum();
+        let mut a = Matrix::new(rows, columns);
+        let (mut r, mut c) = (0, 0);
+        for b in blocks {
+            for (i, row) in b.entries.iter().enumerate() {
+                a.entries[r + i][c..c + b.columns].clone_from_slice(row);
+            }
+            r += b.rows;
+            c += b.columns;
+        }
+        a
+    }
+
+    /// 特性行列 xI - A。正方行列でなければpanicする。
+    pub fn characteristic_matrix(&self) -> Matrix<Polynomial<T>> {
+        assert!(
+            self.is_square(),
+            "characteristic matrix of a non-square matrix"
+        );
+        let entries = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, a)| {
+                        let c = Polynomial::new_constant(-a.clone());
+                        if i == j {
+                            c + Polynomial::x()
+                        } else {
+                            c
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Matrix {
+            entries,
+            rows: self.rows,
+            columns: self.columns,
+        }
+    }
+
+    /// 列ベクトル v に左から掛ける
+    fn mul_vector(&self, v: &[T]) -> Vec<T> {
+        self.entries
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(v)
+                    .fold(T::zero(), |acc, (a, x)| acc + a.clone() * x.clone())
+            })
+            .collect()
+    }
+
+    /// 和。型が違えば```DimensionError```を返す。
+    pub fn checked_add(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, DimensionError> {
+        self.check_same_shape(rhs)?;
+        let mut ans = self.clone();
+        for (row, other) in ans.entries.iter_mut().zip(&rhs.entries) {
+            for (entry, b) in row.iter_mut().zip(other) {
+                *entry += b.clone();
+            }
+        }
+        Ok(ans)
+    }
+
+    /// 差。型が違えば```DimensionError```を返す。
+    pub fn checked_sub(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, DimensionError> {
+        self.check_same_shape(rhs)?;
+        let mut ans = self.clone();
+        for (row, other) in ans.entries.iter_mut().zip(&rhs.entries) {
+            for (entry, b) in row.iter_mut().zip(other) {
+                *entry -= b.clone();
+            }
+        }
+        Ok(ans)
+    }
+
+    /// 積。左の列数と右の行数が違えば```DimensionError```を返す。
+    pub fn checked_mul(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, DimensionError> {
+        if self.columns != rhs.rows {
+            return Err(DimensionError::new(self, rhs));
+        }
+        Ok(Matrix::mul(self, rhs, T::zero()))
+    }
+
+    /// 繰り返し二乗法によるべき乗。正方行列でなければ```DimensionError```を返す。
+    pub fn checked_pow(&self, p: u64) -> Result<Matrix<T>, DimensionError> {
+        if !self.is_square() {
+            return Err(DimensionError::new(self, self));
+        }
+        Ok(Matrix::mat_pow(self, p, T::zero(), T::identity()))
+    }
+
+    /// 繰り返し二乗法によるべき乗。正方行列でなければpanicする。
+    pub fn pow(&self, p: u64) -> Matrix<T> {
+        self.checked_pow(p)
+            .expect("matrix power of a non-square matrix")
+    }
+
+    fn check_same_shape(&self, rhs: &Matrix<T>) -> Result<(), DimensionError> {
+        if self.rows == rhs.rows && self.columns == rhs.columns {
+            Ok(())
+        } else {
+            Err(DimensionError::new(self, rhs))
+        }
+    }
+
     pub fn change_entry(&mut self, r: usize, c: usize, n: T) {
         self.entries[r][c] = n;
     }
@@ -39,19 +239,20 @@ impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Identit
         self.entries[r][c] += n;
     }
 
+    /// 積。型が合わないときは0×0行列を返す（エラーにしたいときは```checked_mul()```や```*```を使う）。
     pub fn mul(a: &Matrix<T>, b: &Matrix<T>, zero: T) -> Matrix<T> {
         if a.columns != b.rows {
             Matrix {
-                entries: vec![vec![zero; 0]; 0],
+                entries: Vec::new(),
                 rows: 0,
                 columns: 0,
             }
         } else {
             let mut v = vec![vec![zero; b.columns]; a.rows];
-            for i in 0..(a.rows) {
-                for j in 0..(b.columns) {
+            for (i, row) in v.iter_mut().enumerate() {
+                for (j, entry) in row.iter_mut().enumerate() {
                     for k in 0..(a.columns) {
-                        v[i][j] += a.entries[i][k] * b.entries[k][j];
+                        *entry += a.entries[i][k].clone() * b.entries[k][j].clone();
                     }
                 }
             }
@@ -64,40 +265,40 @@ impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Identit
     }
 
     pub fn mul_ch(&mut self, b: &Matrix<T>, zero: T) {
-        if self.columns != b.rows {
-            return;
-        } else {
+        if self.columns == b.rows {
             let mut v = vec![vec![zero; b.columns]; self.rows];
-            for i in 0..(self.rows) {
-                for j in 0..(b.columns) {
+            for (i, row) in v.iter_mut().enumerate() {
+                for (j, entry) in row.iter_mut().enumerate() {
                     for k in 0..(self.columns) {
-                        v[i][j] += self.entries[i][k] * b.entries[k][j];
+                        *entry += self.entries[i][k].clone() * b.entries[k][j].clone();
                     }
                 }
             }
             self.entries = v;
+            self.columns = b.columns;
         }
     }
 
+    /// べき乗。正方行列でないときは0×0行列を返す（エラーにしたいときは```checked_pow()```を使う）。
     pub fn mat_pow(a: &Matrix<T>, p: u64, zero: T, one: T) -> Matrix<T> {
         if a.columns != a.rows {
             Matrix {
-                entries: vec![vec![zero; 0]; 0],
+                entries: Vec::new(),
                 rows: 0,
                 columns: 0,
             }
         } else {
             let n = a.rows;
-            let mut ans = Matrix::new_id(zero, one, n);
+            let mut ans = Matrix::new_id(zero.clone(), one, n);
             let mut now = a.clone();
             let mut res = p;
             while res != 0 {
                 let now_copy = now.clone();
                 if res & 1 != 0 {
-                    ans.mul_ch(&now_copy, zero);
+                    ans.mul_ch(&now_copy, zero.clone());
                 }
                 res >>= 1;
-                now.mul_ch(&now_copy, zero);
+                now.mul_ch(&now_copy, zero.clone());
             }
             Matrix {
                 entries: ans.entries,
@@ -107,3 +308,1582 @@ impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Identit
         }
     }
 }
+
+/// 掃き出し法（ガウスの消去法）を使うもの。成分が体の元であることを仮定。
+impl<T: Field> Matrix<T> {
+    /// 行基本変形で簡約階段形にする。（簡約階段形, 主成分のある列の番号, 行列式に掛かる値）を返す。
+    /// 行列式に掛かる値は、行の入れ替えによる符号と主成分の積。
+    fn eliminate(&self) -> (Matrix<T>, Vec<usize>, T) {
+        let mut a = self.clone();
+        let mut pivots = Vec::new();
+        let mut factor = T::identity();
+        for c in 0..a.columns {
+            let r = pivots.len();
+            if r == a.rows {
+                break;
+            }
+            let p = match (r..a.rows).find(|&i| a.entries[i][c] != T::zero()) {
+                Some(p) => p,
+                None => continue,
+            };
+            if p != r {
+                a.entries.swap(p, r);
+                factor = -factor;
+            }
+            let pivot = a.entries[r][c].clone();
+            let inv = pivot
+                .clone()
+                .inverse()
+                .expect("non-zero element of a field must be invertible");
+            factor *= pivot;
+            for entry in a.entries[r].iter_mut() {
+                *entry *= inv.clone();
+            }
+            let pivot_row = a.entries[r].clone();
+            for (i, row) in a.entries.iter_mut().enumerate() {
+                if i == r || row[c] == T::zero() {
+                    continue;
+                }
+                let m = row[c].clone();
+                for (entry, x) in row.iter_mut().zip(&pivot_row) {
+                    *entry -= m.clone() * x.clone();
+                }
+            }
+            pivots.push(c);
+        }
+        (a, pivots, factor)
+    }
+
+    /// 簡約階段形（reduced row echelon form）
+    pub fn rref(&self) -> Matrix<T> {
+        self.eliminate().0
+    }
+
+    /// 階数
+    pub fn rank(&self) -> usize {
+        self.eliminate().1.len()
+    }
+
+    /// 行列式。正方行列でなければpanicする。
+    pub fn determinant(&self) -> T {
+        assert!(self.is_square(), "determinant of a non-square matrix");
+        let (_, pivots, factor) = self.eliminate();
+        if pivots.len() == self.rows {
+            factor
+        } else {
+            T::zero()
+        }
+    }
+
+    /// 核（Ax = 0 の解空間）の基底
+    pub fn kernel(&self) -> Vec<Vec<T>> {
+        let (a, pivots, _) = self.eliminate();
+        Matrix::kernel_from_rref(&a, &pivots, self.columns)
+    }
+
+    /// Ax = b を解く。解があれば（特殊解, 核の基底）を返し、解がなければ```None```。
+    /// 解全体は特殊解に核の元を足したもの。```b```の長さが行数と違えばpanicする。
+    pub fn solve(&self, b: &[T]) -> Option<(Vec<T>, Vec<Vec<T>>)> {
+        assert_eq!(
+            b.len(),
+            self.rows,
+            "length of b must equal the number of rows"
+        );
+        let n = self.columns;
+        let augmented = Matrix {
+            entries: self
+                .entries
+                .iter()
+                .zip(b)
+                .map(|(row, x)| {
+                    let mut row = row.clone();
+                    row.push(x.clone());
+                    row
+                })
+                .collect(),
+            rows: self.rows,
+            columns: n + 1,
+        };
+        let (a, pivots, _) = augmented.eliminate();
+        if pivots.last() == Some(&n) {
+            return None;
+        }
+        let mut x = vec![T::zero(); n];
+        for (k, &c) in pivots.iter().enumerate() {
+            x[c] = a.entries[k][n].clone();
+        }
+        Some((x, Matrix::kernel_from_rref(&a, &pivots, n)))
+    }
+
+    /// 特性多項式 det(xI - A)。相似変換でヘッセンベルグ行列にしてから漸化式で求める。O(n^3)。
+    /// 正方行列でなければpanicする。
+    pub fn characteristic_polynomial_hessenberg(&self) -> Polynomial<T> {
+        assert!(
+            self.is_square(),
+            "characteristic polynomial of a non-square matrix"
+        );
+        let n = self.rows;
+        let mut h = self.entries.clone();
+        // 上ヘッセンベルグ行列（対角の2つ以上下が0）にする
+        for j in 0..n.saturating_sub(2) {
+            let p = match (j + 1..n).find(|&i| h[i][j] != T::zero()) {
+                Some(p) => p,
+                None => continue,
+            };
+            if p != j + 1 {
+                h.swap(p, j + 1);
+                for row in h.iter_mut() {
+                    row.swap(p, j + 1);
+                }
+            }
+            let inv = h[j + 1][j]
+                .clone()
+                .inverse()
+                .expect("non-zero element of a field must be invertible");
+            for k in j + 2..n {
+                let u = h[k][j].clone() * inv.clone();
+                if u == T::zero() {
+                    continue;
+                }
+                // k行目から(j+1)行目のu倍を引き、(j+1)列目にk列目のu倍を足す
+                let pivot_row = h[j + 1].clone();
+                for (entry, x) in h[k].iter_mut().zip(pivot_row) {
+                    *entry -= u.clone() * x;
+                }
+                for row in h.iter_mut() {
+                    let x = row[k].clone();
+                    row[j + 1] += u.clone() * x;
+                }
+            }
+        }
+        // p_{m+1} = (x - h_{m,m}) p_m - Σ_{i<m} h_{i,m} h_{i+1,i} ... h_{m,m-1} p_i
+        let x = Polynomial::x();
+        let mut p = vec![Polynomial::identity()];
+        for m in 0..n {
+            let mut next = (x.clone() - Polynomial::new_constant(h[m][m].clone())) * p[m].clone();
+            let mut t = T::identity();
+            for i in (0..m).rev() {
+                t *= h[i + 1][i].clone();
+                next -= p[i].clone() * Polynomial::new_constant(h[i][m].clone() * t.clone());
+            }
+            p.push(next);
+        }
+        p.pop().unwrap()
+    }
+
+    /// 最小多項式（f(A) = 0 となるmonicな多項式で次数最小のもの）。正方行列でなければpanicする。
+    ///
+    /// I, A, A^2, ... を順に並べていき、初めて一次従属になったときの関係式から求める。
+    pub fn minimal_polynomial(&self) -> Polynomial<T> {
+        assert!(
+            self.is_square(),
+            "minimal polynomial of a non-square matrix"
+        );
+        let n = self.rows;
+        let flatten = |a: &Matrix<T>| -> Vec<T> { a.entries.concat() };
+        let mut powers = vec![flatten(&Matrix::identity(n))];
+        let mut a = Matrix::identity(n);
+        loop {
+            // 各 A^k を列として並べた n^2 × (k+1) 行列の核を見る
+            let k = powers.len();
+            let krylov = Matrix {
+                entries: (0..n * n)
+                    .map(|i| powers.iter().map(|v| v[i].clone()).collect())
+                    .collect(),
+                rows: n * n,
+                columns: k,
+            };
+            if let Some(v) = krylov.kernel().pop() {
+                // A^{k-1} までは一次独立だったので、最後の係数は0でない
+                return Polynomial::new(&v).monic();
+            }
+            a = Matrix::mul(&a, self, T::zero());
+            powers.push(flatten(&a));
+        }
+    }
+
+    /// フロベニウス標準形（有理標準形）。（F, P）で、Pは可逆、P^{-1}AP = F となるもの。正方行列でなければpanicする。
+    ///
+    /// Fは xI - A の単因子のうち1でないもの d_1 | d_2 | ... の同伴行列を対角に並べたもの。
+    /// xI - A のスミス標準形を U(xI - A)V = D とすると、U^{-1} の各列の x に A を代入して得られるベクトルが
+    /// 各ブロックの巡回ベクトルになる。
+    pub fn frobenius_normal_form(&self) -> (Matrix<T>, Matrix<T>) {
+        let n = self.rows;
+        let (d, _, u_inv, _) = self.characteristic_matrix().smith();
+        let (d, u_inv) = (d.entries, u_inv.entries);
+        let mut blocks = Vec::new();
+        let mut columns = Vec::new();
+        for (i, row) in d.iter().enumerate() {
+            let f = &row[i];
+            if f.deg() == 0 {
+                continue;
+            }
+            // g = Σ_j (U^{-1})_{ji}(A) e_j をホーナー法で求める
+            let k = (0..n).map(|j| u_inv[j][i].deg()).max().unwrap_or(0);
+            let mut g = vec![T::zero(); n];
+            for l in (0..=k).rev() {
+                g = self.mul_vector(&g);
+                for (j, x) in g.iter_mut().enumerate() {
+                    *x += u_inv[j][i].coeff(l);
+                }
+            }
+            // g, Ag, ..., A^{deg f - 1}g を P の列にする
+            for _ in 0..f.deg() {
+                let next = self.mul_vector(&g);
+                columns.push(g);
+                g = next;
+            }
+            blocks.push(Matrix::companion(f));
+        }
+        (
+            Matrix::block_diagonal(&blocks),
+            Matrix::from_rows(&columns).transpose(),
+        )
+    }
+
+    /// 相似かどうか（P^{-1}AP = B となる可逆行列Pがあるかどうか）。xI - A と xI - B の単因子を比べる。
+    /// どちらかが正方行列でなければ```false```。
+    pub fn is_similar(&self, other: &Matrix<T>) -> bool {
+        self.is_square()
+            && other.is_square()
+            && self.rows == other.rows
+            && self.characteristic_matrix().invariant_factors()
+                == other.characteristic_matrix().invariant_factors()
+    }
+
+    /// P^{-1}AP = B（Aは```self```、Bは```other```）となる可逆行列P。相似でなければ```None```。
+    /// 両方をフロベニウス標準形に移す変換をつなげて作る。
+    pub fn similarity_transform(&self, other: &Matrix<T>) -> Option<Matrix<T>> {
+        if !(self.is_square() && other.is_square() && self.rows == other.rows) {
+            return None;
+        }
+        let (f, p) = self.frobenius_normal_form();
+        let (g, q) = other.frobenius_normal_form();
+        if f != g {
+            return None;
+        }
+        Some(Matrix::mul(&p, &q.inverse()?, T::zero()))
+    }
+
+    /// ジョルダン標準形。（J, P）で、Pは可逆、P^{-1}AP = J となるもの。正方行列でなければpanicする。
+    ///
+    /// ```eigenvalues```には固有値の候補を渡す（重複しているものや固有値でないものは無視する）。
+    /// 特性多項式がそれらの1次式の積に分解しなければ```None```。
+    /// 係数体で分解しないときは、```map()```で```QuadField```などの拡大体の行列にしてから、そこでの固有値を渡す。
+    /// ジョルダン細胞は```eigenvalues```の順に、同じ固有値の中では大きいものから並べる。
+    pub fn jordan_normal_form_with(&self, eigenvalues: &[T]) -> Option<(Matrix<T>, Matrix<T>)> {
+        assert!(
+            self.is_square(),
+            "Jordan normal form of a non-square matrix"
+        );
+        let mut f = self.characteristic_polynomial_hessenberg();
+        let mut blocks = Vec::new();
+        let mut columns = Vec::new();
+        for (k, lambda) in eigenvalues.iter().enumerate() {
+            if eigenvalues[..k].contains(lambda) {
+                continue;
+            }
+            // 特性多項式を x - λ で割れるだけ割って重複度を調べる
+            let linear = Polynomial::new(&[-lambda.clone(), T::identity()]);
+            let mut m = 0;
+            loop {
+                let (q, r) = Polynomial::div_rem(&f, &linear);
+                if r.strict_deg().is_some() {
+                    break;
+                }
+                f = q;
+                m += 1;
+            }
+            if m == 0 {
+                continue;
+            }
+            for chain in self.jordan_chains(lambda, m) {
+                blocks.push(Matrix::jordan_block(lambda.clone(), chain.len()));
+                columns.extend(chain);
+            }
+        }
+        if columns.len() < self.rows {
+            return None;
+        }
+        Some((
+            Matrix::block_diagonal(&blocks),
+            Matrix::from_rows(&columns).transpose(),
+        ))
+    }
+
+    /// 重複度 m の固有値 λ の広義固有空間の基底を、ジョルダン鎖に分けて長い順に返す。
+    /// 各鎖は N = A - λI として N^{k-1}v, ..., Nv, v の順。
+    fn jordan_chains(&self, lambda: &T, m: usize) -> Vec<Vec<Vec<T>>> {
+        let n = self.rows;
+        let mut nil = self.clone();
+        for i in 0..n {
+            nil.entries[i][i] -= lambda.clone();
+        }
+        // kernels[k] は ker N^k の基底。次元が m になるまで求める。
+        let mut kernels = vec![Vec::new()];
+        let mut power = Matrix::identity(n);
+        while kernels.last().map_or(0, |k: &Vec<Vec<T>>| k.len()) < m {
+            power = Matrix::mul(&power, &nil, T::zero());
+            kernels.push(power.kernel());
+        }
+        let top = kernels.len() - 1;
+        // levels[k] は選んだ鎖のうち ker N^k に入り ker N^{k-1} に入らないベクトル
+        let mut levels: Vec<Vec<Vec<T>>> = vec![Vec::new(); top + 1];
+        let mut chains = Vec::new();
+        for k in (1..=top).rev() {
+            for b in &kernels[k] {
+                // ker N^{k-1} とすでに選んだベクトルで張れるものは飛ばす
+                let mut rows = kernels[k - 1].clone();
+                rows.extend(levels[k].iter().cloned());
+                let r = rows.len();
+                rows.push(b.clone());
+                if Matrix::from_rows(&rows).rank() == r {
+                    continue;
+                }
+                let mut chain = vec![b.clone()];
+                for _ in 1..k {
+                    let v = nil.mul_vector(chain.last().unwrap());
+                    chain.push(v);
+                }
+                chain.reverse();
+                for (j, v) in chain.iter().enumerate() {
+                    levels[j + 1].push(v.clone());
+                }
+                chains.push(chain);
+            }
+        }
+        chains
+    }
+
+    /// 簡約階段形の最初の```n```列から核の基底を作る。主成分のない列ごとに1つ。
+    fn kernel_from_rref(a: &Matrix<T>, pivots: &[usize], n: usize) -> Vec<Vec<T>> {
+        (0..n)
+            .filter(|c| !pivots.contains(c))
+            .map(|f| {
+                let mut v = vec![T::zero(); n];
+                v[f] = T::identity();
+                for (k, &c) in pivots.iter().enumerate() {
+                    v[c] = -a.entries[k][f].clone();
+                }
+                v
+            })
+            .collect()
+    }
+}
+
+/// 割り算を使わないもの。成分が可換環の元であることを仮定。
+impl<T: CommutativeRing> Matrix<T> {
+    /// Berkowitz のアルゴリズムで det(xI - A) の係数を最高次から順に求める。O(n^4)。
+    ///
+    /// 左上のk×k部分の特性多項式に、k+1行目とk+1列目から作る Toeplitz 行列を掛けて次を求める。
+    fn berkowitz(&self) -> Vec<T> {
+        assert!(
+            self.is_square(),
+            "characteristic polynomial of a non-square matrix"
+        );
+        let mut p = vec![T::identity()];
+        for m in 0..self.rows {
+            // Toeplitz 行列の第1列：1, -a_mm, -RC, -RAC, -RA^2C, ...
+            // （Aは左上のm×m部分、Rはm行目の左側、Cはm列目の上側）
+            let mut t = vec![T::identity(), -self.entries[m][m].clone()];
+            let mut v: Vec<T> = (0..m).map(|i| self.entries[i][m].clone()).collect();
+            for _ in 0..m {
+                let rv = (0..m).fold(T::zero(), |acc, j| {
+                    acc + self.entries[m][j].clone() * v[j].clone()
+                });
+                t.push(-rv);
+                v = (0..m)
+                    .map(|i| {
+                        (0..m).fold(T::zero(), |acc, j| {
+                            acc + self.entries[i][j].clone() * v[j].clone()
+                        })
+                    })
+                    .collect();
+            }
+            p = (0..=m + 1)
+                .map(|i| {
+                    (0..=i.min(m)).fold(T::zero(), |acc, j| acc + t[i - j].clone() * p[j].clone())
+                })
+                .collect();
+        }
+        p
+    }
+
+    /// 特性多項式 det(xI - A)。割り算を使わない Berkowitz のアルゴリズムで求めるので、どんな可換環の元が成分でもよい。
+    /// 正方行列でなければpanicする。
+    pub fn characteristic_polynomial(&self) -> Polynomial<T> {
+        let mut p = self.berkowitz();
+        p.reverse();
+        Polynomial::new(&p)
+    }
+
+    /// 割り算を使わない Berkowitz のアルゴリズムによる行列式。正方行列でなければpanicする。
+    /// ```Integer```や```QuadInt```、```Polynomial```など、逆元のない環の元を成分にもつ行列にも使える。
+    pub fn determinant_berkowitz(&self) -> T {
+        let p = self.berkowitz();
+        let det = p[self.rows].clone();
+        if self.rows % 2 == 0 {
+            det
+        } else {
+            -det
+        }
+    }
+
+    /// 余因子行列（adj(A) A = A adj(A) = det(A) I となるもの）。正方行列でなければpanicする。
+    ///
+    /// 特性多項式を x^n + c_{n-1}x^{n-1} + ... + c_0 とすると、ケイリー・ハミルトンの定理から
+    /// adj(A) = (-1)^{n-1} (A^{n-1} + c_{n-1}A^{n-2} + ... + c_1 I) となるので、割り算を使わずに求まる。
+    pub fn adjugate(&self) -> Matrix<T> {
+        let n = self.rows;
+        let p = self.berkowitz();
+        if n == 0 {
+            return self.clone();
+        }
+        let mut b = Matrix::identity(n);
+        for c in &p[1..n] {
+            b = Matrix::mul(&b, self, T::zero());
+            for i in 0..n {
+                b.entries[i][i] += c.clone();
+            }
+        }
+        if n % 2 == 0 {
+            -b
+        } else {
+            b
+        }
+    }
+}
+
+/// 割り切れることが分かっている割り算や互除法を使うもの。成分がユークリッド整域の元であることを仮定。
+impl<T: EuclideanDomain> Matrix<T> {
+    /// エルミート標準形。（H, U）で、Uは可逆（行列式が単元）、UA = H となるもの。
+    ///
+    /// Hは上の行ほど主成分が左にある階段形で、主成分は```normalizing_unit()```で揃え（整数なら正）、
+    /// 主成分より上の成分は主成分で割った余りにしてある。
+    pub fn hermite_normal_form(&self) -> (Matrix<T>, Matrix<T>) {
+        let (m, n) = (self.rows, self.columns);
+        let mut h = self.entries.clone();
+        let mut u = Matrix::<T>::identity(m).entries;
+        let mut r = 0;
+        for c in 0..n {
+            if r == m {
+                break;
+            }
+            // 互除法で c 列目の r 行目より下を0にする
+            for i in r + 1..m {
+                if h[i][c] != T::zero() {
+                    let x = gcd_transform(&h[r][c], &h[i][c]);
+                    combine_rows(&mut h, r, i, &x);
+                    combine_rows(&mut u, r, i, &x);
+                }
+            }
+            if h[r][c] == T::zero() {
+                continue;
+            }
+            let unit = h[r][c].normalizing_unit();
+            scale_row(&mut h, r, &unit);
+            scale_row(&mut u, r, &unit);
+            for i in 0..r {
+                let q = T::div_rem(&h[i][c], &h[r][c]).0;
+                if q != T::zero() {
+                    let x = [T::identity(), -q, T::zero(), T::identity()];
+                    combine_rows(&mut h, i, r, &x);
+                    combine_rows(&mut u, i, r, &x);
+                }
+            }
+            r += 1;
+        }
+        (
+            Matrix {
+                entries: h,
+                rows: m,
+                columns: n,
+            },
+            Matrix {
+                entries: u,
+                rows: m,
+                columns: m,
+            },
+        )
+    }
+
+    /// スミス標準形。（D, U, V）で、U, Vは可逆、UAV = D となるもの。
+    ///
+    /// Dは対角行列で、対角成分 d_1, d_2, ... は d_1 | d_2 | ... を満たし、```normalizing_unit()```で揃えてある。
+    pub fn smith_normal_form(&self) -> (Matrix<T>, Matrix<T>, Matrix<T>) {
+        let (d, u, _, v) = self.smith();
+        (d, u, v)
+    }
+
+    /// スミス標準形の計算。（D, U, U^{-1}, V）を返す。
+    /// U^{-1} には U に掛けた行の変形の逆を右から列の変形として掛けていく。
+    fn smith(&self) -> (Matrix<T>, Matrix<T>, Matrix<T>, Matrix<T>) {
+        let (m, n) = (self.rows, self.columns);
+        let mut d = self.entries.clone();
+        let mut u = Matrix::<T>::identity(m).entries;
+        let mut u_inv = Matrix::<T>::identity(m).entries;
+        let mut v = Matrix::<T>::identity(n).entries;
+        for t in 0..m.min(n) {
+            // 残りの部分でノルム最小の成分を(t, t)に持ってくる
+            let pivot = (t..m)
+                .flat_map(|i| (t..n).map(move |j| (i, j)))
+                .filter(|&(i, j)| d[i][j] != T::zero())
+                .min_by_key(|&(i, j)| d[i][j].norm());
+            let (pi, pj) = match pivot {
+                Some(p) => p,
+                None => break,
+            };
+            d.swap(t, pi);
+            u.swap(t, pi);
+            for row in u_inv.iter_mut() {
+                row.swap(t, pi);
+            }
+            for row in d.iter_mut().chain(v.iter_mut()) {
+                row.swap(t, pj);
+            }
+            loop {
+                for i in t + 1..m {
+                    if d[i][t] != T::zero() {
+                        let x = gcd_transform(&d[t][t], &d[i][t]);
+                        combine_rows(&mut d, t, i, &x);
+                        combine_rows(&mut u, t, i, &x);
+                        combine_columns(&mut u_inv, t, i, &inverse_transform(&x));
+                    }
+                }
+                for j in t + 1..n {
+                    if d[t][j] != T::zero() {
+                        let x = gcd_transform(&d[t][t], &d[t][j]);
+                        combine_columns(&mut d, t, j, &x);
+                        combine_columns(&mut v, t, j, &x);
+                    }
+                }
+                // 列の変形で t 列目が崩れていたらやり直す
+                if (t + 1..m).any(|i| d[i][t] != T::zero()) {
+                    continue;
+                }
+                // (t, t)成分で割り切れない成分があれば、その行を t 行目に足してやり直す
+                let indivisible = (t + 1..m)
+                    .find(|&i| (t + 1..n).any(|j| T::div_rem(&d[i][j], &d[t][t]).1 != T::zero()));
+                match indivisible {
+                    Some(i) => {
+                        let x = [T::identity(), T::identity(), T::zero(), T::identity()];
+                        combine_rows(&mut d, t, i, &x);
+                        combine_rows(&mut u, t, i, &x);
+                        combine_columns(&mut u_inv, t, i, &inverse_transform(&x));
+                    }
+                    None => break,
+                }
+            }
+            let unit = d[t][t].normalizing_unit();
+            scale_row(&mut d, t, &unit);
+            scale_row(&mut u, t, &unit);
+            scale_column(&mut u_inv, t, &T::div_rem(&T::identity(), &unit).0);
+        }
+        let square = |entries, n| Matrix {
+            entries,
+            rows: n,
+            columns: n,
+        };
+        (
+            Matrix {
+                entries: d,
+                rows: m,
+                columns: n,
+            },
+            square(u, m),
+            square(u_inv, m),
+            square(v, n),
+        )
+    }
+
+    /// 単因子（スミス標準形の0でない対角成分）。
+    /// 整数行列なら、行ベクトルで生成される部分群による Z^n の剰余群は Z/d_1 × Z/d_2 × ... × Z^(n - 階数) になる。
+    pub fn invariant_factors(&self) -> Vec<T> {
+        let (d, _, _) = self.smith_normal_form();
+        (0..self.rows.min(self.columns))
+            .map(|i| d.entries[i][i].clone())
+            .filter(|x| *x != T::zero())
+            .collect()
+    }
+
+    /// 核（Ax = 0 となる x 全体のなす加群。整数行列なら整数解全体）の基底。
+    /// 転置のエルミート標準形で0になった行に対応する変換行列の行を取り出す。
+    pub fn module_kernel(&self) -> Vec<Vec<T>> {
+        let (h, u) = self.transpose().hermite_normal_form();
+        (0..h.rows)
+            .filter(|&i| h.entries[i].iter().all(|x| *x == T::zero()))
+            .map(|i| u.entries[i].clone())
+            .collect()
+    }
+
+    /// Bareiss のアルゴリズムによる行列式。O(n^3)。正方行列でなければpanicする。
+    ///
+    /// 途中の割り算は必ず割り切れるので、```Integer```や体係数の```Polynomial```の行列でも分数を使わずに計算できる。
+    pub fn determinant_bareiss(&self) -> T {
+        assert!(self.is_square(), "determinant of a non-square matrix");
+        let n = self.rows;
+        if n == 0 {
+            return T::identity();
+        }
+        let mut a = self.entries.clone();
+        let mut negative = false;
+        let mut previous = T::identity();
+        for k in 0..n - 1 {
+            if a[k][k] == T::zero() {
+                match (k + 1..n).find(|&i| a[i][k] != T::zero()) {
+                    Some(i) => {
+                        a.swap(i, k);
+                        negative = !negative;
+                    }
+                    None => return T::zero(),
+                }
+            }
+            for i in k + 1..n {
+                for j in k + 1..n {
+                    let x = a[i][j].clone() * a[k][k].clone() - a[i][k].clone() * a[k][j].clone();
+                    a[i][j] = T::div_rem(&x, &previous).0;
+                }
+            }
+            previous = a[k][k].clone();
+        }
+        let det = a[n - 1][n - 1].clone();
+        if negative {
+            -det
+        } else {
+            det
+        }
+    }
+}
+
+/// s a + t b = g（gは a と b の最大公約数）となる s, t を使って、(a, b) を (g, 0) に移す
+/// 行列式1の変換 (s, t; -b/g, a/g) を作る。bは0でないとする。
+fn gcd_transform<T: EuclideanDomain>(a: &T, b: &T) -> [T; 4] {
+    let (g, s, t) = T::extended_gcd(a, b);
+    let a_g = T::div_rem(a, &g).0;
+    let b_g = T::div_rem(b, &g).0;
+    [s, t, -b_g, a_g]
+}
+
+/// i行目とj行目を混ぜる。新しいi行目は s (i行目) + t (j行目)、新しいj行目は u (i行目) + v (j行目)。
+fn combine_rows<T: Ring>(a: &mut [Vec<T>], i: usize, j: usize, [s, t, u, v]: &[T; 4]) {
+    let (ri, rj) = if i < j {
+        let (lo, hi) = a.split_at_mut(j);
+        (&mut lo[i], &mut hi[0])
+    } else {
+        let (lo, hi) = a.split_at_mut(i);
+        (&mut hi[0], &mut lo[j])
+    };
+    for (x, y) in ri.iter_mut().zip(rj.iter_mut()) {
+        let (p, q) = (x.clone(), y.clone());
+        *x = s.clone() * p.clone() + t.clone() * q.clone();
+        *y = u.clone() * p + v.clone() * q;
+    }
+}
+
+/// i列目とj列目を```combine_rows()```と同じように混ぜる。
+fn combine_columns<T: Ring>(a: &mut [Vec<T>], i: usize, j: usize, [s, t, u, v]: &[T; 4]) {
+    for row in a.iter_mut() {
+        let (p, q) = (row[i].clone(), row[j].clone());
+        row[i] = s.clone() * p.clone() + t.clone() * q.clone();
+        row[j] = u.clone() * p + v.clone() * q;
+    }
+}
+
+/// 行列式1の変換 (s, t; u, v) の逆 (v, -t; -u, s) を、右から掛けるときの```combine_columns()```の引数の形で返す。
+fn inverse_transform<T: Ring>([s, t, u, v]: &[T; 4]) -> [T; 4] {
+    [v.clone(), -u.clone(), -t.clone(), s.clone()]
+}
+
+/// i行目に左から c を掛ける
+fn scale_row<T: Ring>(a: &mut [Vec<T>], i: usize, c: &T) {
+    for x in a[i].iter_mut() {
+        *x = c.clone() * x.clone();
+    }
+}
+
+/// i列目に右から c を掛ける
+fn scale_column<T: Ring>(a: &mut [Vec<T>], i: usize, c: &T) {
+    for row in a.iter_mut() {
+        row[i] = row[i].clone() * c.clone();
+    }
+}
+
+/// 多項式の根を自分で求められる型（```Roots```）を成分とする行列
+impl<T: Field + Roots> Matrix<T> {
+    /// 係数体の範囲でのジョルダン標準形。特性多項式の根（```roots()```）を固有値として```jordan_normal_form_with()```を呼ぶ。
+    /// 固有値は```roots()```の順に並べる。係数体の1次式の積に分解しなければ```None```。
+    pub fn jordan_normal_form(&self) -> Option<(Matrix<T>, Matrix<T>)> {
+        // 特性多項式はmonicなので0多項式にはならない
+        let eigenvalues = T::roots(&self.characteristic_polynomial_hessenberg())?;
+        self.jordan_normal_form_with(&eigenvalues)
+    }
+}
+
+/// 浮動小数点数による数値計算。成分が```RealNumber```か```Complex<RealNumber>```であることを仮定。
+///
+/// 丸め誤差があるので、結果の確認には```==```ではなく```approx_eq()```を使う。
+impl<T: Scalar> Matrix<T> {
+    /// 成分ごとの差の絶対値がすべて```tolerance```以下かどうか。型（行数と列数）が違えば```false```。
+    pub fn approx_eq(&self, other: &Matrix<T>, tolerance: f64) -> bool {
+        self.rows == other.rows
+            && self.columns == other.columns
+            && self
+                .entries
+                .iter()
+                .zip(&other.entries)
+                .all(|(r, s)| r.iter().zip(s).all(|(a, b)| a.approx_eq(b, tolerance)))
+    }
+
+    /// 随伴行列（共役転置）A^*。実数なら転置と同じ。
+    pub fn conjugate_transpose(&self) -> Matrix<T> {
+        let mut a = self.transpose();
+        for row in a.entries.iter_mut() {
+            for x in row.iter_mut() {
+                *x = x.conjugate();
+            }
+        }
+        a
+    }
+
+    /// 1ノルム（各列の成分の絶対値の和の最大値）
+    pub fn norm_one(&self) -> f64 {
+        (0..self.columns)
+            .map(|j| self.entries.iter().map(|row| row[j].abs()).sum())
+            .fold(0f64, f64::max)
+    }
+
+    /// フロベニウスノルム（成分の絶対値の2乗和の平方根）
+    pub fn norm_frobenius(&self) -> f64 {
+        self.entries
+            .iter()
+            .flatten()
+            .map(|x| x.abs() * x.abs())
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// 0とみなす大きさ。成分の絶対値の最大値に、行数と列数の大きい方と機械イプシロンを掛けたもの。
+    fn tolerance(&self) -> f64 {
+        let max = self
+            .entries
+            .iter()
+            .flatten()
+            .map(|x| x.abs())
+            .fold(0f64, f64::max);
+        max * self.rows.max(self.columns) as f64 * f64::EPSILON
+    }
+
+    /// 部分ピボット選択付きのLU分解。L（対角成分を除く）とUを重ねた行列と、行の並べ替えを返す。
+    /// 並べ替えの i 番目は、PA の i 行目が A の何行目か。
+    fn lu_factor(&self) -> (Vec<Vec<T>>, Vec<usize>) {
+        assert!(self.is_square(), "LU decomposition of a non-square matrix");
+        let n = self.rows;
+        let tolerance = self.tolerance();
+        let mut a = self.entries.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        for k in 0..n {
+            // k列目で絶対値が最大の成分を主成分に選ぶ
+            let p = (k..n)
+                .max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))
+                .unwrap();
+            a.swap(k, p);
+            permutation.swap(k, p);
+            if a[k][k].abs() <= tolerance {
+                continue;
+            }
+            let inv = a[k][k]
+                .inverse()
+                .expect("non-zero element of a field must be invertible");
+            let pivot_row = a[k].clone();
+            for row in a[k + 1..].iter_mut() {
+                let l = row[k] * inv;
+                row[k] = l;
+                for (x, &u) in row[k + 1..].iter_mut().zip(&pivot_row[k + 1..]) {
+                    *x -= l * u;
+                }
+            }
+        }
+        (a, permutation)
+    }
+
+    /// 部分ピボット選択付きのLU分解。（P, L, U）で、PA = LU となるもの。正方行列でなければpanicする。
+    ///
+    /// Pは置換行列、Lは対角成分が1の下三角行列、Uは上三角行列。各列で絶対値が最大の成分を主成分に選ぶ。
+    pub fn lu(&self) -> (Matrix<T>, Matrix<T>, Matrix<T>) {
+        let n = self.rows;
+        let (a, permutation) = self.lu_factor();
+        let mut p = Matrix::new(n, n);
+        let mut l = Matrix::identity(n);
+        let mut u = Matrix::new(n, n);
+        for i in 0..n {
+            p.entries[i][permutation[i]] = T::identity();
+            l.entries[i][..i].copy_from_slice(&a[i][..i]);
+            u.entries[i][i..].copy_from_slice(&a[i][i..]);
+        }
+        (p, l, u)
+    }
+
+    /// LU分解で Ax = b を解く。主成分の絶対値が丸め誤差程度（```tolerance()```以下）になり、
+    /// 正則とみなせないときは```None```。正方行列でないときや```b```の長さが行数と違うときはpanicする。
+    pub fn lu_solve(&self, b: &[T]) -> Option<Vec<T>> {
+        assert_eq!(
+            b.len(),
+            self.rows,
+            "length of b must equal the number of rows"
+        );
+        let (a, permutation) = self.lu_factor();
+        let tolerance = self.tolerance();
+        if (0..self.rows).any(|i| a[i][i].abs() <= tolerance) {
+            return None;
+        }
+        Some(Matrix::lu_substitute(&a, &permutation, b))
+    }
+
+    /// LU分解の結果を使って前進代入と後退代入をする。
+    fn lu_substitute(a: &[Vec<T>], permutation: &[usize], b: &[T]) -> Vec<T> {
+        let n = a.len();
+        let mut x: Vec<T> = permutation.iter().map(|&i| b[i]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                let y = x[j];
+                x[i] -= a[i][j] * y;
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let y = x[j];
+                x[i] -= a[i][j] * y;
+            }
+            x[i] *= a[i][i]
+                .inverse()
+                .expect("non-zero element of a field must be invertible");
+        }
+        x
+    }
+
+    /// 1ノルムでの条件数 ||A|| ||A^{-1}||。LU分解で逆行列を求めて計算するので、丸め誤差を含んだ推定値になる。
+    /// 正則とみなせないときは```f64::INFINITY```。正方行列でなければpanicする。
+    pub fn condition_number(&self) -> f64 {
+        let n = self.rows;
+        let (a, permutation) = self.lu_factor();
+        let tolerance = self.tolerance();
+        if (0..n).any(|i| a[i][i].abs() <= tolerance) {
+            return f64::INFINITY;
+        }
+        let inverse_norm = (0..n)
+            .map(|j| {
+                let mut e = vec![T::zero(); n];
+                e[j] = T::identity();
+                Matrix::lu_substitute(&a, &permutation, &e)
+                    .iter()
+                    .map(|x| x.abs())
+                    .sum()
+            })
+            .fold(0f64, f64::max);
+        self.norm_one() * inverse_norm
+    }
+
+    /// ハウスホルダー変換によるQR分解。（Q, R）で、Qはユニタリ行列（実数なら直交行列）、
+    /// Rは上三角行列（対角より下が0）、A = QR となるもの。A が m×n なら Q は m×m、R は m×n。
+    pub fn qr(&self) -> (Matrix<T>, Matrix<T>) {
+        let (m, n) = (self.rows, self.columns);
+        let mut r = self.entries.clone();
+        let mut q = Matrix::<T>::identity(m).entries;
+        for k in 0..n.min(m.saturating_sub(1)) {
+            let norm = (k..m).map(|i| r[i][k].abs().powi(2)).sum::<f64>().sqrt();
+            if norm == 0f64 {
+                continue;
+            }
+            // 打ち消し合いを避けるため、x_k と同じ向きに ||x|| だけずらす
+            let x = r[k][k];
+            let phase = if x.abs() == 0f64 {
+                T::identity()
+            } else {
+                x * T::from_f64(1f64 / x.abs())
+            };
+            let mut v: Vec<T> = (k..m).map(|i| r[i][k]).collect();
+            v[0] += phase * T::from_f64(norm);
+            let v_norm2: f64 = v.iter().map(|x| x.abs().powi(2)).sum();
+            let c = T::from_f64(2f64 / v_norm2);
+            // R ← (I - 2vv^* / v^*v) R
+            for j in k..n {
+                let s = v
+                    .iter()
+                    .zip(&r[k..])
+                    .fold(T::zero(), |acc, (v, row)| acc + v.conjugate() * row[j]);
+                for (v, row) in v.iter().zip(r[k..].iter_mut()) {
+                    row[j] -= c * *v * s;
+                }
+            }
+            // Q ← Q (I - 2vv^* / v^*v)
+            for row in q.iter_mut() {
+                let s = v
+                    .iter()
+                    .zip(&row[k..])
+                    .fold(T::zero(), |acc, (v, x)| acc + *x * *v);
+                for (v, x) in v.iter().zip(row[k..].iter_mut()) {
+                    *x -= c * s * v.conjugate();
+                }
+            }
+            for row in r[k + 1..].iter_mut() {
+                row[k] = T::zero();
+            }
+        }
+        (
+            Matrix {
+                entries: q,
+                rows: m,
+                columns: m,
+            },
+            Matrix {
+                entries: r,
+                rows: m,
+                columns: n,
+            },
+        )
+    }
+
+    /// 最小二乗法。||Ax - b|| を最小にする x をQR分解で求める。
+    /// 行数が列数より少ないときや、列が（丸め誤差の範囲で）一次従属のときは```None```。
+    /// ```b```の長さが行数と違えばpanicする。
+    pub fn least_squares(&self, b: &[T]) -> Option<Vec<T>> {
+        assert_eq!(
+            b.len(),
+            self.rows,
+            "length of b must equal the number of rows"
+        );
+        let (m, n) = (self.rows, self.columns);
+        if m < n {
+            return None;
+        }
+        let (q, r) = self.qr();
+        let tolerance = self.tolerance();
+        if (0..n).any(|i| r.entries[i][i].abs() <= tolerance) {
+            return None;
+        }
+        // Q^* b の最初の n 成分について Rx = Q^* b を後退代入で解く
+        let mut x: Vec<T> = (0..n)
+            .map(|j| (0..m).fold(T::zero(), |acc, i| acc + q.entries[i][j].conjugate() * b[i]))
+            .collect();
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let y = x[j];
+                x[i] -= r.entries[i][j] * y;
+            }
+            x[i] *= r.entries[i][i]
+                .inverse()
+                .expect("non-zero element of a field must be invertible");
+        }
+        Some(x)
+    }
+
+    /// コレスキー分解。A = LL^* となる、対角成分が正の実数の下三角行列L。
+    /// Aはエルミート行列（実数なら対称行列）とし、下三角部分だけを見る。
+    /// 正定値とみなせないときは```None```。正方行列でなければpanicする。
+    pub fn cholesky(&self) -> Option<Matrix<T>> {
+        assert!(
+            self.is_square(),
+            "Cholesky decomposition of a non-square matrix"
+        );
+        let n = self.rows;
+        let tolerance = self.tolerance();
+        let mut l = Matrix::<T>::new(n, n);
+        for j in 0..n {
+            let d = self.entries[j][j].real_part()
+                - (0..j).map(|k| l.entries[j][k].abs().powi(2)).sum::<f64>();
+            if d.is_nan() || d <= tolerance {
+                return None;
+            }
+            let d = d.sqrt();
+            l.entries[j][j] = T::from_f64(d);
+            for i in j + 1..n {
+                let s = (0..j).fold(self.entries[i][j], |acc, k| {
+                    acc - l.entries[i][k] * l.entries[j][k].conjugate()
+                });
+                l.entries[i][j] = s * T::from_f64(1f64 / d);
+            }
+        }
+        Some(l)
+    }
+}
+
+/// 実数成分の行列の固有値と特異値。内部では```f64```で計算する。
+impl Matrix<RealNumber> {
+    /// 成分を```f64```にした配列
+    fn to_f64_entries(&self) -> Vec<Vec<f64>> {
+        self.entries
+            .iter()
+            .map(|row| row.iter().map(|x| x.to_f64()).collect())
+            .collect()
+    }
+
+    /// ```f64```の配列から作る
+    fn from_f64_entries(entries: Vec<Vec<f64>>, rows: usize, columns: usize) -> Self {
+        Matrix {
+            entries: entries
+                .into_iter()
+                .map(|row| row.into_iter().map(RealNumber::new).collect())
+                .collect(),
+            rows,
+            columns,
+        }
+    }
+
+    /// 対称行列の固有値分解（ヤコビ法）。（固有値, V）で、固有値は小さい順、
+    /// Vは対応する単位固有ベクトルを列に並べた直交行列で、A = V diag(固有値) V^T となるもの。
+    /// Aは対称行列であるとする。正方行列でなければpanicする。
+    pub fn symmetric_eigendecomposition(&self) -> (Vec<RealNumber>, Matrix<RealNumber>) {
+        assert!(
+            self.is_square(),
+            "eigendecomposition of a non-square matrix"
+        );
+        let n = self.rows;
+        let mut a = self.to_f64_entries();
+        let mut v = Matrix::<RealNumber>::identity(n).to_f64_entries();
+        let tolerance = self.norm_frobenius() * f64::EPSILON;
+        for _ in 0..100 {
+            let off: f64 = (0..n)
+                .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+                .map(|(i, j)| a[i][j] * a[i][j])
+                .sum();
+            if off.sqrt() <= tolerance {
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[p][q] == 0f64 {
+                        continue;
+                    }
+                    // (p, q)成分を0にする回転角 θ について t = tan θ
+                    let theta = (a[q][q] - a[p][p]) / (2f64 * a[p][q]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1f64).sqrt());
+                    let c = 1f64 / (t * t + 1f64).sqrt();
+                    let s = t * c;
+                    for row in a.iter_mut().chain(v.iter_mut()) {
+                        let (x, y) = (row[p], row[q]);
+                        row[p] = c * x - s * y;
+                        row[q] = s * x + c * y;
+                    }
+                    let (lo, hi) = a.split_at_mut(q);
+                    for (x, y) in lo[p].iter_mut().zip(hi[0].iter_mut()) {
+                        let (u, w) = (*x, *y);
+                        *x = c * u - s * w;
+                        *y = s * u + c * w;
+                    }
+                }
+            }
+        }
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| a[i][i].total_cmp(&a[j][j]));
+        let values = order.iter().map(|&i| RealNumber::new(a[i][i])).collect();
+        let vectors = v
+            .iter()
+            .map(|row| order.iter().map(|&i| row[i]).collect())
+            .collect();
+        (values, Matrix::from_f64_entries(vectors, n, n))
+    }
+
+    /// 固有値（重複度込み）。実数でない固有値もあるので```Complex<RealNumber>```で返し、実部、虚部の小さい順に並べる。
+    /// ヘッセンベルグ行列に相似変換してから、Francis のダブルシフト付きQR法で求める。
+    /// 収束しなかったときは```None```。正方行列でなければpanicする。
+    pub fn eigenvalues(&self) -> Option<Vec<Complex<RealNumber>>> {
+        assert!(self.is_square(), "eigenvalues of a non-square matrix");
+        let n = self.rows;
+        let mut a = self.to_f64_entries();
+        hessenberg(&mut a);
+        let norm: f64 = (0..n)
+            .flat_map(|i| (i.saturating_sub(1)..n).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j].abs())
+            .sum();
+        let mut values = Vec::with_capacity(n);
+        // これまでのシフトの和
+        let mut t = 0f64;
+        let mut iterations = 0;
+        // 左上 hi × hi の部分の固有値がまだ求まっていない
+        let mut hi = n;
+        while hi > 0 {
+            let nn = hi - 1;
+            // 下から見て、対角の1つ下が十分小さいところで切る
+            let mut l = nn;
+            while l > 0 {
+                let mut s = a[l - 1][l - 1].abs() + a[l][l].abs();
+                if s == 0f64 {
+                    s = norm;
+                }
+                if a[l][l - 1].abs() + s == s {
+                    a[l][l - 1] = 0f64;
+                    break;
+                }
+                l -= 1;
+            }
+            let mut x = a[nn][nn];
+            if l == nn {
+                // 1×1 のブロックが分離した
+                values.push((x + t, 0f64));
+                hi -= 1;
+                iterations = 0;
+                continue;
+            }
+            let mut y = a[nn - 1][nn - 1];
+            let mut w = a[nn][nn - 1] * a[nn - 1][nn];
+            if l == nn - 1 {
+                // 2×2 のブロックが分離したので、2次方程式を解く
+                let p = 0.5 * (y - x);
+                let q = p * p + w;
+                let z = q.abs().sqrt();
+                x += t;
+                if q >= 0f64 {
+                    let z = if p >= 0f64 { p + z } else { p - z };
+                    values.push((x + z, 0f64));
+                    values.push((if z != 0f64 { x - w / z } else { x + z }, 0f64));
+                } else {
+                    values.push((x + p, z));
+                    values.push((x + p, -z));
+                }
+                hi -= 2;
+                iterations = 0;
+                continue;
+            }
+            if iterations == 60 {
+                return None;
+            }
+            if iterations == 10 || iterations == 20 || iterations == 40 {
+                // なかなか収束しないときは例外的なシフトを使う
+                t += x;
+                for (i, row) in a.iter_mut().enumerate().take(hi) {
+                    row[i] -= x;
+                }
+                let s = a[nn][nn - 1].abs() + a[nn - 1][nn - 2].abs();
+                x = 0.75 * s;
+                y = x;
+                w = -0.4375 * s * s;
+            }
+            iterations += 1;
+            // 対角の2つ下が小さくなるところを探し、そこからQRステップを始める
+            let mut m = nn - 2;
+            let (mut p, mut q, mut r);
+            loop {
+                let z = a[m][m];
+                let r0 = x - z;
+                let s0 = y - z;
+                p = (r0 * s0 - w) / a[m + 1][m] + a[m][m + 1];
+                q = a[m + 1][m + 1] - z - r0 - s0;
+                r = a[m + 2][m + 1];
+                let s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                let u = a[m][m - 1].abs() * (q.abs() + r.abs());
+                let v = p.abs() * (a[m - 1][m - 1].abs() + z.abs() + a[m + 1][m + 1].abs());
+                if u + v == v {
+                    break;
+                }
+                m -= 1;
+            }
+            for i in m + 2..=nn {
+                a[i][i - 2] = 0f64;
+                if i != m + 2 {
+                    a[i][i - 3] = 0f64;
+                }
+            }
+            // ハウスホルダー変換で膨らみを右下へ追い出していく
+            for k in m..nn {
+                if k != m {
+                    p = a[k][k - 1];
+                    q = a[k + 1][k - 1];
+                    r = if k != nn - 1 { a[k + 2][k - 1] } else { 0f64 };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x != 0f64 {
+                        p /= x;
+                        q /= x;
+                        r /= x;
+                    }
+                }
+                let s = (p * p + q * q + r * r).sqrt();
+                let s = if p >= 0f64 { s } else { -s };
+                if s == 0f64 {
+                    continue;
+                }
+                if k == m {
+                    if l != m {
+                        a[k][k - 1] = -a[k][k - 1];
+                    }
+                } else {
+                    a[k][k - 1] = -s * x;
+                }
+                p += s;
+                x = p / s;
+                y = q / s;
+                let z = r / s;
+                q /= p;
+                r /= p;
+                let (top, rest) = a.split_at_mut(k + 1);
+                let (next, rest) = rest.split_at_mut(1);
+                let rows = top[k][k..=nn].iter_mut().zip(next[0][k..=nn].iter_mut());
+                if k != nn - 1 {
+                    for ((a0, a1), a2) in rows.zip(rest[0][k..=nn].iter_mut()) {
+                        let p = *a0 + q * *a1 + r * *a2;
+                        *a2 -= p * z;
+                        *a1 -= p * y;
+                        *a0 -= p * x;
+                    }
+                } else {
+                    for (a0, a1) in rows {
+                        let p = *a0 + q * *a1;
+                        *a1 -= p * y;
+                        *a0 -= p * x;
+                    }
+                }
+                for row in a.iter_mut().take(nn.min(k + 3) + 1).skip(l) {
+                    let mut p = x * row[k] + y * row[k + 1];
+                    if k != nn - 1 {
+                        p += z * row[k + 2];
+                        row[k + 2] -= p * r;
+                    }
+                    row[k + 1] -= p * q;
+                    row[k] -= p;
+                }
+            }
+        }
+        values.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        Some(
+            values
+                .into_iter()
+                .map(|(re, im)| Complex::new(RealNumber::new(re), RealNumber::new(im)))
+                .collect(),
+        )
+    }
+
+    /// 特異値分解（片側ヤコビ法）。A が m×n、k = min(m, n) のとき（U, 特異値, V）で、
+    /// U は m×k、V は n×k の列が正規直交な行列、特異値は大きい順の k 個、A = U diag(特異値) V^T となるもの。
+    pub fn singular_value_decomposition(
+        &self,
+    ) -> (Matrix<RealNumber>, Vec<RealNumber>, Matrix<RealNumber>) {
+        let (m, n) = (self.rows, self.columns);
+        if m < n {
+            // A^T = U Σ V^T なら A = V Σ U^T
+            let (u, sigma, v) = self.transpose().singular_value_decomposition();
+            return (v, sigma, u);
+        }
+        // 列ベクトルどうしが直交するまで2列ずつ回転する。V も列を行として持っておく。
+        let mut columns = self.transpose().to_f64_entries();
+        let mut v = Matrix::<RealNumber>::identity(n).to_f64_entries();
+        let dot = |x: &[f64], y: &[f64]| -> f64 { x.iter().zip(y).map(|(a, b)| a * b).sum() };
+        for _ in 0..100 {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let alpha = dot(&columns[p], &columns[p]);
+                    let beta = dot(&columns[q], &columns[q]);
+                    let gamma = dot(&columns[p], &columns[q]);
+                    if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+                    let zeta = (beta - alpha) / (2f64 * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (zeta * zeta + 1f64).sqrt());
+                    let c = 1f64 / (t * t + 1f64).sqrt();
+                    let s = t * c;
+                    for w in [&mut columns, &mut v] {
+                        let (lo, hi) = w.split_at_mut(q);
+                        for (x, y) in lo[p].iter_mut().zip(hi[0].iter_mut()) {
+                            let (a, b) = (*x, *y);
+                            *x = c * a - s * b;
+                            *y = s * a + c * b;
+                        }
+                    }
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+        let norms: Vec<f64> = columns.iter().map(|c| dot(c, c).sqrt()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+        let tolerance = norms.iter().fold(0f64, |a, &b| a.max(b)) * m as f64 * f64::EPSILON;
+        // 特異値が0の列は、ほかの列と直交する単位ベクトルで補う
+        let mut u: Vec<Vec<f64>> = Vec::with_capacity(n);
+        for &j in &order {
+            if norms[j] > tolerance {
+                u.push(columns[j].iter().map(|x| x / norms[j]).collect());
+                continue;
+            }
+            let e = (0..m)
+                .map(|i| {
+                    let mut e = vec![0f64; m];
+                    e[i] = 1f64;
+                    for b in &u {
+                        let d = dot(&e, b);
+                        for (x, y) in e.iter_mut().zip(b) {
+                            *x -= d * y;
+                        }
+                    }
+                    e
+                })
+                .max_by(|x, y| dot(x, x).total_cmp(&dot(y, y)))
+                .unwrap();
+            let norm = dot(&e, &e).sqrt();
+            u.push(e.iter().map(|x| x / norm).collect());
+        }
+        let sigma = order
+            .iter()
+            .map(|&j| RealNumber::new(if norms[j] > tolerance { norms[j] } else { 0f64 }))
+            .collect();
+        let v = order.iter().map(|&j| v[j].clone()).collect();
+        (
+            Matrix::from_f64_entries(u, n, m).transpose(),
+            sigma,
+            Matrix::from_f64_entries(v, n, n).transpose(),
+        )
+    }
+
+    /// 特異値（大きい順に min(m, n) 個）
+    pub fn singular_values(&self) -> Vec<RealNumber> {
+        self.singular_value_decomposition().1
+    }
+}
+
+/// 行の入れ替え付きの消去による相似変換で上ヘッセンベルグ行列（対角の2つ以上下が0）にする。
+fn hessenberg(a: &mut [Vec<f64>]) {
+    let n = a.len();
+    for m in 1..n.saturating_sub(1) {
+        // m-1 列目の m 行目以降で絶対値最大のものを m 行目に持ってくる
+        let i = (m..n)
+            .max_by(|&i, &j| a[i][m - 1].abs().total_cmp(&a[j][m - 1].abs()))
+            .unwrap();
+        let x = a[i][m - 1];
+        if i != m {
+            a.swap(i, m);
+            for row in a.iter_mut() {
+                row.swap(i, m);
+            }
+        }
+        if x == 0f64 {
+            continue;
+        }
+        for i in m + 1..n {
+            let y = a[i][m - 1] / x;
+            if y == 0f64 {
+                continue;
+            }
+            // i行目から m行目の y 倍を引き、m列目に i列目の y 倍を足す
+            let pivot_row = a[m].clone();
+            for (entry, p) in a[i].iter_mut().zip(pivot_row) {
+                *entry -= y * p;
+            }
+            for row in a.iter_mut() {
+                row[m] += y * row[i];
+            }
+        }
+    }
+}
+
+impl<T> Matrix<T> {
+    /// 各成分に```f```を施した行列。```QuadField```などの拡大体の行列に移すときに使う。
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Matrix<U> {
+        Matrix {
+            entries: self
+                .entries
+                .iter()
+                .map(|row| row.iter().map(&f).collect())
+                .collect(),
+            rows: self.rows,
+            columns: self.columns,
+        }
+    }
+
+    /// 各成分を```item```で文字列にして並べる。1行ずつ ```[1, 2]``` のように書いて改行で区切る。
+    /// ```Notation::Latex```のときは```pmatrix```環境で書く。
+    pub fn to_string_with<F: Fn(&T) -> String>(&self, notation: Notation, item: F) -> String {
+        let rows = self
+            .entries
+            .iter()
+            .map(|row| row.iter().map(&item).collect::<Vec<_>>());
+        match notation {
+            Notation::Latex => format!(
+                "\\begin{{pmatrix}} {} \\end{{pmatrix}}",
+                rows.map(|row| row.join(" & "))
+                    .collect::<Vec<_>>()
+                    .join(" \\\\ ")
+            ),
+            _ => rows
+                .map(|row| format!("[{}]", row.join(", ")))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+/// 1行ずつ ```[1, 2]``` のように書いて改行で区切る。
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.to_string_with(Notation::Plain, |t| t.to_string())
+        )
+    }
+}
+
+/// ```pmatrix```環境で書く。
+impl<T: ToLatex> ToLatex for Matrix<T> {
+    fn to_latex(&self) -> String {
+        self.to_string_with(Notation::Latex, T::to_latex)
+    }
+}
+
+/// 行列の型（行数と列数）が演算に合わないときのエラー。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DimensionError {
+    /// 左側の（行数, 列数）
+    pub left: (usize, usize),
+    /// 右側の（行数, 列数）
+    pub right: (usize, usize),
+}
+
+impl DimensionError {
+    fn new<T>(a: &Matrix<T>, b: &Matrix<T>) -> Self {
+        Self {
+            left: (a.rows, a.columns),
+            right: (b.rows, b.columns),
+        }
+    }
+}
+
+impl fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "dimension mismatch: {}x{} and {}x{}",
+            self.left.0, self.left.1, self.right.0, self.right.1
+        )
+    }
+}
+
+impl Error for DimensionError {}
+
+/// 逆行列。正方行列でないときや正則でないときは```None```を返す。
+impl<T: Field> Inverse for Matrix<T> {
+    fn inverse(self) -> Option<Self> {
+        if !self.is_square() {
+            return None;
+        }
+        let n = self.rows;
+        let augmented = Matrix {
+            entries: self
+                .entries
+                .into_iter()
+                .enumerate()
+                .map(|(i, mut row)| {
+                    row.extend((0..n).map(|j| if i == j { T::identity() } else { T::zero() }));
+                    row
+                })
+                .collect(),
+            rows: n,
+            columns: 2 * n,
+        };
+        let (a, pivots, _) = augmented.eliminate();
+        if pivots.iter().filter(|&&c| c < n).count() < n {
+            return None;
+        }
+        Some(Matrix {
+            entries: a.entries.into_iter().map(|row| row[n..].to_vec()).collect(),
+            rows: n,
+            columns: n,
+        })
+    }
+}
+
+/// (i, j)成分
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.entries[i][j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.entries[i][j]
+    }
+}
+
+/// 足し算の実装。型が違えばpanicする（panicさせたくないときは```checked_add()```を使う）。
+impl<T: Ring> Add for Matrix<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(&rhs).expect("matrix addition")
+    }
+}
+
+impl<T: Ring> AddAssign for Matrix<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.checked_add(&rhs).expect("matrix addition");
+    }
+}
+
+/// 引き算の実装。型が違えばpanicする（panicさせたくないときは```checked_sub()```を使う）。
+impl<T: Ring> Sub for Matrix<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.checked_sub(&rhs).expect("matrix subtraction")
+    }
+}
+
+impl<T: Ring> SubAssign for Matrix<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.checked_sub(&rhs).expect("matrix subtraction");
+    }
+}
+
+/// 掛け算の実装。型が合わなければpanicする（panicさせたくないときは```checked_mul()```を使う）。
+impl<T: Ring> Mul for Matrix<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.checked_mul(&rhs).expect("matrix multiplication")
+    }
+}
+
+impl<T: Ring> MulAssign for Matrix<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.checked_mul(&rhs).expect("matrix multiplication");
+    }
+}
+
+/// スカラー倍（各成分に右から掛ける）
+impl<T: Ring> Mul<T> for Matrix<T> {
+    type Output = Self;
+    fn mul(mut self, rhs: T) -> Self {
+        self *= rhs;
+        self
+    }
+}
+
+impl<T: Ring> MulAssign<T> for Matrix<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        for row in self.entries.iter_mut() {
+            for entry in row.iter_mut() {
+                *entry *= rhs.clone();
+            }
+        }
+    }
+}
+
+impl<T: AdditiveGroup> Neg for Matrix<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Matrix {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|row| row.into_iter().map(|entry| -entry).collect())
+                .collect(),
+            rows: self.rows,
+            columns: self.columns,
+        }
+    }
+}
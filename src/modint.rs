@@ -1,8 +1,15 @@
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, Field, IntegralDomain, Ring};
 use crate::characteristic::Characteristic;
+use crate::convolution::{convolution_mod, karatsuba, ntt_root};
 use crate::identities::{Identity, Zero};
 use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::number_theory::{add_mod, inverse_mod, mul_mod, pow_mod, sqrt_mod, sub_mod};
+use crate::parse::{parse_integer, strip_delimiters, trim, ParseError};
+use crate::square_root::SquareRoot;
 use std::fmt;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// ```MOD```で割った余り。Z / MOD Z の元。
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -11,6 +18,9 @@ pub struct ModInt<const MOD: u64> {
 }
 
 impl<const MOD: u64> ModInt<MOD> {
+    /// 畳み込みのNTTに使う1の冪根。```MOD```ごとにコンパイル時に計算される。
+    const NTT_ROOT: Option<(u64, u32)> = ntt_root(MOD);
+
     /// コンストラクタ。代表元が一意になるよう```0```以上```MOD```未満の整数として格納。
     pub fn new(n: u64) -> Self {
         ModInt {
@@ -25,23 +35,15 @@ impl<const MOD: u64> ModInt<MOD> {
 
     /// 繰り返し二乗法によるべき乗の計算
     pub fn modpow(&self, n: u64) -> Self {
-        let mut res = 1;
-        let mut a = self.representative;
-        let mut m = n;
-        loop {
-            if m == 0 {
-                break;
-            }
-            if m % 2 == 1 {
-                res = (res * a) % MOD;
-            }
-            a = (a * a) % MOD;
-            m = m / 2;
-        }
         ModInt {
-            representative: res,
+            representative: pow_mod(self.representative, n, MOD),
         }
     }
+
+    /// 割り算。割る元が可逆でないときは```None```を返す。
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        rhs.inverse().map(|inv| self * inv)
+    }
 }
 
 /// ```println!```などで見やすく表示させるため、```Display```トレイトを実装。
@@ -51,11 +53,29 @@ impl<const MOD: u64> fmt::Display for ModInt<MOD> {
     }
 }
 
+/// ```[3]```（```Display```の形式）や```3```、```-3```を読む。代表元でない整数は```MOD```で割った余りにする。
+impl<const MOD: u64> FromStr for ModInt<MOD> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (t, o) = trim(s, 0);
+        let (t, o) = strip_delimiters(t, '[', ']', o).unwrap_or((t, o));
+        let n = parse_integer(t, o)?;
+        Ok(ModInt::new(n.rem_euclid(MOD as i128) as u64))
+    }
+}
+
+/// 剰余類であることが分かるよう```\\overline{3}```のように書く。
+impl<const MOD: u64> ToLatex for ModInt<MOD> {
+    fn to_latex(&self) -> String {
+        format!("\\overline{{{}}}", self.representative)
+    }
+}
+
 impl<const MOD: u64> Add for ModInt<MOD> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
         ModInt {
-            representative: (self.representative + rhs.representative) % MOD,
+            representative: add_mod(self.representative, rhs.representative, MOD),
         }
     }
 }
@@ -63,7 +83,7 @@ impl<const MOD: u64> Add for ModInt<MOD> {
 impl<const MOD: u64> AddAssign for ModInt<MOD> {
     fn add_assign(&mut self, other: Self) {
         *self = Self {
-            representative: (self.representative + other.representative) % MOD,
+            representative: add_mod(self.representative, other.representative, MOD),
         };
     }
 }
@@ -72,7 +92,7 @@ impl<const MOD: u64> Sub for ModInt<MOD> {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output {
         ModInt {
-            representative: (self.representative + MOD - rhs.representative) % MOD,
+            representative: sub_mod(self.representative, rhs.representative, MOD),
         }
     }
 }
@@ -80,7 +100,7 @@ impl<const MOD: u64> Sub for ModInt<MOD> {
 impl<const MOD: u64> SubAssign for ModInt<MOD> {
     fn sub_assign(&mut self, other: Self) {
         *self = Self {
-            representative: (self.representative + MOD - other.representative) % MOD,
+            representative: sub_mod(self.representative, other.representative, MOD),
         };
     }
 }
@@ -89,7 +109,7 @@ impl<const MOD: u64> Mul for ModInt<MOD> {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
         ModInt {
-            representative: (self.representative * rhs.representative) % MOD,
+            representative: mul_mod(self.representative, rhs.representative, MOD),
         }
     }
 }
@@ -97,11 +117,25 @@ impl<const MOD: u64> Mul for ModInt<MOD> {
 impl<const MOD: u64> MulAssign for ModInt<MOD> {
     fn mul_assign(&mut self, other: Self) {
         *self = Self {
-            representative: (self.representative * other.representative) % MOD,
+            representative: mul_mod(self.representative, other.representative, MOD),
         };
     }
 }
 
+/// 割り算の実装。割る元が可逆でない（```MOD```と互いに素でない）ときはpanicする。
+impl<const MOD: u64> Div for ModInt<MOD> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("division by a non-unit")
+    }
+}
+
+impl<const MOD: u64> DivAssign for ModInt<MOD> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
 /// unary negation の実装
 impl<const MOD: u64> Neg for ModInt<MOD> {
     type Output = Self;
@@ -122,15 +156,18 @@ impl<const MOD: u64> Identity for ModInt<MOD> {
     }
 }
 
+/// 逆元の実装。拡張ユークリッドの互除法で a x ≡ 1 (mod MOD) となる x を求めるので、```MOD```が合成数でもよい。
 impl<const MOD: u64> Inverse for ModInt<MOD> {
     fn inverse(self) -> Option<ModInt<MOD>> {
-        let n = self.to_int();
-        if num::Integer::gcd(&n, &MOD) != 1 {
-            None
-        } else {
-            let ret = self.modpow(MOD - 2);
-            Some(ret)
-        }
+        inverse_mod(self.representative, MOD).map(|n| ModInt { representative: n })
+    }
+}
+
+/// 平方根の実装。トネリ–シャンクスのアルゴリズムによるので、```MOD```が素数でないときは（0を除いて）```None```を返す。
+/// 2つある平方根のうち代表元の小さい方を返す。
+impl<const MOD: u64> SquareRoot for ModInt<MOD> {
+    fn square_root(self) -> Option<ModInt<MOD>> {
+        sqrt_mod(self.representative, MOD).map(|n| ModInt { representative: n })
     }
 }
 
@@ -139,3 +176,31 @@ impl<const MOD: u64> Characteristic for ModInt<MOD> {
         MOD
     }
 }
+
+impl<const MOD: u64> AdditiveGroup for ModInt<MOD> {}
+
+/// 畳み込みは、使えるときはNTTで計算する。
+impl<const MOD: u64> Ring for ModInt<MOD> {
+    fn convolution(a: &[Self], b: &[Self]) -> Vec<Self> {
+        let x: Vec<u64> = a.iter().map(|c| c.representative).collect();
+        let y: Vec<u64> = b.iter().map(|c| c.representative).collect();
+        match convolution_mod(&x, &y, MOD, Self::NTT_ROOT) {
+            Some(v) => v
+                .into_iter()
+                .map(|n| ModInt { representative: n })
+                .collect(),
+            None => karatsuba(a, b),
+        }
+    }
+}
+
+impl<const MOD: u64> CommutativeRing for ModInt<MOD> {}
+
+/// ```MOD```が素数のときに限り整域（体）になる。素数であることは使う側が保証する。
+impl<const MOD: u64> IntegralDomain for ModInt<MOD> {}
+
+/// ```MOD```が素数のときに限り体になる。素数であることは使う側が保証する。
+///
+/// ```MOD```が合成数のときも```Inverse```は可逆でない元に```None```を返すので、
+/// 体を仮定するアルゴリズム（多項式の割り算や最大公約数など）は、可逆でない元で割ろうとした時点でpanicする。
+impl<const MOD: u64> Field for ModInt<MOD> {}
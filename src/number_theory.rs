@@ -0,0 +1,232 @@
+// u64の範囲での剰余演算や素数まわり。ModInt, DynModInt, F_p まわりや畳み込みで共有する。
+// ModIntの法に対する前計算をコンパイル時に行えるよう、できるものはconst fnにしておく。
+
+/// 法```m```での足し算。```a```, ```b```は```m```未満とする。```m```が2^63を超えても桁あふれしないようにする。
+pub(crate) const fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    let (s, overflow) = a.overflowing_add(b);
+    if overflow || s >= m {
+        s.wrapping_sub(m)
+    } else {
+        s
+    }
+}
+
+/// 法```m```での引き算。```a```, ```b```は```m```未満とする。
+pub(crate) const fn sub_mod(a: u64, b: u64, m: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + (m - b)
+    }
+}
+
+/// 法```m```での掛け算。```m```が2^32を超えるときは```u128```に広げて計算する。
+pub(crate) const fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    if m <= 1 << 32 {
+        a * b % m
+    } else {
+        (a as u128 * b as u128 % m as u128) as u64
+    }
+}
+
+/// 繰り返し二乗法による a^n mod m の計算。
+pub(crate) const fn pow_mod(a: u64, n: u64, m: u64) -> u64 {
+    let mut res = 1 % m;
+    let mut a = a % m;
+    let mut n = n;
+    while n != 0 {
+        if n & 1 == 1 {
+            res = mul_mod(res, a, m);
+        }
+        a = mul_mod(a, a, m);
+        n >>= 1;
+    }
+    res
+}
+
+/// 拡張ユークリッドの互除法で a x ≡ 1 (mod m) となる x を求める。```a```と```m```が互いに素でなければ```None```。
+pub(crate) fn inverse_mod(a: u64, m: u64) -> Option<u64> {
+    let (mut r0, mut r1) = (m as i128, a as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 != 1 {
+        None
+    } else {
+        Some(t0.rem_euclid(m as i128) as u64)
+    }
+}
+
+/// 素数判定。ミラー–ラビン素数判定法で、u64の範囲では決定的に判定できる底（37以下の素数）を使う。
+pub const fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    // const fn にするため、forではなくwhileで回す
+    let mut i = 0;
+    while i < BASES.len() {
+        if n == BASES[i] {
+            return true;
+        }
+        if n % BASES[i] == 0 {
+            return false;
+        }
+        i += 1;
+    }
+    // n - 1 = d * 2^s (dは奇数)
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mut i = 0;
+    'base: while i < BASES.len() {
+        let mut x = pow_mod(BASES[i], d, n);
+        i += 1;
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'base;
+            }
+            r += 1;
+        }
+        return false;
+    }
+    true
+}
+
+/// ポラード・ロー法（ブレントの循環検出を使う）で、合成数```n```の自明でない約数を1つ見つける。
+fn pollard_rho(n: u64) -> u64 {
+    if n % 2 == 0 {
+        return 2;
+    }
+    const BLOCK: u64 = 128;
+    for c in 1.. {
+        let f = |x: u64| add_mod(mul_mod(x, x, n), c % n, n);
+        let mut y = 2;
+        let mut x = y;
+        let mut ys = y;
+        let mut g = 1;
+        let mut q = 1;
+        let mut r = 1;
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BLOCK.min(r - k) {
+                    y = f(y);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = num::Integer::gcd(&q, &n);
+                k += BLOCK;
+            }
+            r *= 2;
+        }
+        if g == n {
+            // まとめて掛けたせいで n になってしまったときは1歩ずつやり直す
+            loop {
+                ys = f(ys);
+                g = num::Integer::gcd(&x.abs_diff(ys), &n);
+                if g != 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
+/// 素因数分解。```(素数, 指数)```の組を素数の小さい順に並べて返す。```n```が0や1のときは空にする。
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut primes: Vec<u64> = Vec::new();
+    let mut stack: Vec<u64> = Vec::new();
+    if n > 1 {
+        stack.push(n);
+    }
+    while let Some(m) = stack.pop() {
+        if is_prime(m) {
+            primes.push(m);
+        } else {
+            let d = pollard_rho(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+    primes.sort_unstable();
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+/// 素数```p```の最小の原始根を返す。```p - 1```を素因数分解して、各素因数```q```について g^((p-1)/q) ≠ 1 を確かめる。
+pub fn primitive_root(p: u64) -> u64 {
+    if p == 2 {
+        return 1;
+    }
+    let factors = factorize(p - 1);
+    (2..p)
+        .find(|&g| {
+            factors
+                .iter()
+                .all(|&(q, _)| pow_mod(g, (p - 1) / q, p) != 1)
+        })
+        .unwrap()
+}
+
+/// 素数```p```を法とする平方根。x^2 ≡ a (mod p) となる x のうち小さい方を返し、なければ```None```。
+/// ```a```は```p```未満とする。トネリ–シャンクスのアルゴリズムによる。
+/// ```p```が素数でないとき（```a```が0の場合を除く）は、平方根があっても```None```を返す。
+pub(crate) fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+    // 合成数だと平方非剰余の探索が止まらないことがあるので、先に弾く
+    if !is_prime(p) {
+        return None;
+    }
+    // オイラーの規準で平方剰余かどうかを判定
+    if pow_mod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    // p - 1 = q 2^s (qは奇数)
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p).find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1)?;
+    let mut m = s;
+    let mut c = pow_mod(z, q, p);
+    let mut t = pow_mod(a, q, p);
+    // qは奇数なので (q + 1) / 2 = q / 2 + 1
+    let mut r = pow_mod(a, q / 2 + 1, p);
+    while t != 1 {
+        // t^(2^i) = 1 となる最小の i を探す
+        let mut i = 0;
+        let mut u = t;
+        while u != 1 {
+            u = mul_mod(u, u, p);
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        r = mul_mod(r, b, p);
+    }
+    Some(r.min(p - r))
+}
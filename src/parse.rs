@@ -0,0 +1,275 @@
+use crate::identities::Identity;
+use std::error::Error;
+use std::fmt;
+use std::ops::Neg;
+use std::str::FromStr;
+
+/// 文字列から値を読み取る（```FromStr```）ときのエラー。
+///
+/// ```position```は入力文字列の中で問題が見つかった位置（バイト単位、0始まり）。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    kind: ParseErrorKind,
+}
+
+/// ```ParseError```の種類
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// 何も書かれていない（空の項など）
+    Empty,
+    /// その位置に来るはずのない文字
+    UnexpectedCharacter(char),
+    /// 閉じていない括弧
+    UnclosedDelimiter(char),
+    /// 数として読めない、または型の範囲に収まらない
+    InvalidNumber,
+    /// 分母が0
+    ZeroDenominator,
+    /// ```DynModInt```の法が設定されていない、または```QuadExt```の割る多項式が書かれていない
+    ModulusNotSet,
+    /// ```QuadExt```の割る多項式が x^2 - bx - c の形でない
+    InvalidModulus,
+}
+
+impl ParseError {
+    pub fn new(position: usize, kind: ParseErrorKind) -> Self {
+        Self { position, kind }
+    }
+
+    /// 問題が見つかった位置
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// エラーの種類
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// 部分文字列を読んだときのエラーを、元の文字列での位置に直す。
+    pub(crate) fn shift(self, offset: usize) -> Self {
+        Self {
+            position: self.position + offset,
+            kind: self.kind,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "empty term at position {}", self.position),
+            ParseErrorKind::UnexpectedCharacter(c) => {
+                write!(f, "unexpected '{}' at position {}", c, self.position)
+            }
+            ParseErrorKind::UnclosedDelimiter(c) => {
+                write!(f, "unclosed '{}' at position {}", c, self.position)
+            }
+            ParseErrorKind::InvalidNumber => {
+                write!(f, "invalid number at position {}", self.position)
+            }
+            ParseErrorKind::ZeroDenominator => {
+                write!(f, "zero denominator at position {}", self.position)
+            }
+            ParseErrorKind::ModulusNotSet => write!(f, "modulus is not set"),
+            ParseErrorKind::InvalidModulus => write!(
+                f,
+                "modulus at position {} is not of the form x^2 - bx - c",
+                self.position
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// 前後の空白を取り除き、取り除いた後の文字列と元の文字列での開始位置を返す。
+pub(crate) fn trim(s: &str, offset: usize) -> (&str, usize) {
+    let start = s.len() - s.trim_start().len();
+    (s.trim(), offset + start)
+}
+
+/// 括弧の対応を調べ、```s```の```i```バイト目の開き括弧に対応する閉じ括弧の位置を返す。
+fn matching_delimiter(s: &str, i: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (j, c) in s[i..].char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + j);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 全体が```open```と```close```で囲まれているとき、その内側（と開始位置）を返す。
+/// 囲まれていないときは```None```。
+pub(crate) fn strip_delimiters(
+    s: &str,
+    open: char,
+    close: char,
+    offset: usize,
+) -> Option<(&str, usize)> {
+    if s.starts_with(open) && s.ends_with(close) && matching_delimiter(s, 0) == Some(s.len() - 1) {
+        Some((&s[1..s.len() - 1], offset + 1))
+    } else {
+        None
+    }
+}
+
+/// 全体を囲んでいる丸括弧を（何重でも）外す。
+pub(crate) fn strip_parentheses(s: &str, offset: usize) -> (&str, usize) {
+    let (mut s, mut offset) = trim(s, offset);
+    while let Some((inner, o)) = strip_delimiters(s, '(', ')', offset) {
+        let (t, o) = trim(inner, o);
+        s = t;
+        offset = o;
+    }
+    (s, offset)
+}
+
+/// 符号付き整数を読む。```(-3)```や```-(3)```のように括弧で囲まれていてもよい。
+pub(crate) fn parse_integer(s: &str, offset: usize) -> Result<i128, ParseError> {
+    let (s, offset) = strip_parentheses(s, offset);
+    if s.is_empty() {
+        return Err(ParseError::new(offset, ParseErrorKind::Empty));
+    }
+    // ```-(3)```のように符号の後ろが括弧で囲まれていてもよい
+    let (negative, digits, start) = match s.strip_prefix('-') {
+        Some(t) => {
+            let (t, o) = strip_parentheses(t, offset + 1);
+            (true, t, o)
+        }
+        None => match s.strip_prefix('+') {
+            Some(t) => {
+                let (t, o) = strip_parentheses(t, offset + 1);
+                (false, t, o)
+            }
+            None => (false, s, offset),
+        },
+    };
+    if digits.is_empty() {
+        return Err(ParseError::new(start, ParseErrorKind::Empty));
+    }
+    if let Some((i, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(ParseError::new(
+            start + i,
+            ParseErrorKind::UnexpectedCharacter(c),
+        ));
+    }
+    let value: i128 = digits
+        .parse()
+        .map_err(|_| ParseError::new(start, ParseErrorKind::InvalidNumber))?;
+    Ok(if negative { -value } else { value })
+}
+
+/// 整数を読んで```i64```の範囲に収まるか確かめる。
+pub(crate) fn parse_i64(s: &str, offset: usize) -> Result<i64, ParseError> {
+    let (t, o) = trim(s, offset);
+    let value = parse_integer(t, o)?;
+    if value < i64::MIN as i128 || value > i64::MAX as i128 {
+        return Err(ParseError::new(o, ParseErrorKind::InvalidNumber));
+    }
+    Ok(value as i64)
+}
+
+/// 括弧の外にある2項演算子としての```+```, ```-```で区切って項に分ける。
+///
+/// 各項は（負号が付いているか, 項の文字列, 元の文字列での開始位置）。
+/// 先頭や演算子の直後の```-```は単項の負号として項の側に残す。
+pub(crate) fn split_terms(s: &str, offset: usize) -> Result<Vec<(bool, &str, usize)>, ParseError> {
+    let mut terms = Vec::new();
+    let mut stack = Vec::new();
+    let mut negative = false;
+    let mut start = 0;
+    // 直前の空白でない文字。演算子の直後かどうかの判定に使う。
+    let mut previous: Option<char> = None;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '[' | '{' => stack.push((i, c)),
+            ')' | ']' | '}' => {
+                let expected = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                match stack.pop() {
+                    Some((_, open)) if open == expected => {}
+                    _ => {
+                        return Err(ParseError::new(
+                            offset + i,
+                            ParseErrorKind::UnexpectedCharacter(c),
+                        ))
+                    }
+                }
+            }
+            '+' | '-' if stack.is_empty() => {
+                let binary = match previous {
+                    None => false,
+                    Some(p) => !matches!(p, '+' | '-' | '*' | '/' | '^'),
+                };
+                if binary {
+                    terms.push(term(&s[start..i], negative, offset + start)?);
+                    negative = c == '-';
+                    start = i + 1;
+                }
+            }
+            _ => {}
+        }
+        if !c.is_whitespace() {
+            previous = Some(c);
+        }
+    }
+    if let Some((i, open)) = stack.pop() {
+        return Err(ParseError::new(
+            offset + i,
+            ParseErrorKind::UnclosedDelimiter(open),
+        ));
+    }
+    terms.push(term(&s[start..], negative, offset + start)?);
+    Ok(terms)
+}
+
+fn term(s: &str, negative: bool, offset: usize) -> Result<(bool, &str, usize), ParseError> {
+    let (t, o) = trim(s, offset);
+    if t.is_empty() {
+        Err(ParseError::new(o, ParseErrorKind::Empty))
+    } else {
+        Ok((negative, t, o))
+    }
+}
+
+/// 係数の後ろに付いた変数名（```x```や```i```）を取り除いて係数の部分を返す。
+/// 係数の部分が空（```x```だけ）や```-```だけのときも、そのまま返す。
+/// ```var```で終わっていなければ```None```。
+pub(crate) fn strip_variable(s: &str, var: char) -> Option<&str> {
+    let t = s.strip_suffix(var)?;
+    let t = t.trim_end();
+    Some(t.strip_suffix('*').map_or(t, |u| u.trim_end()))
+}
+
+/// 変数の前に付いている係数を読む。空なら1、```-```だけなら-1とみなす。
+pub(crate) fn parse_coefficient<T>(s: &str, offset: usize) -> Result<T, ParseError>
+where
+    T: Identity + Neg<Output = T> + FromStr<Err = ParseError>,
+{
+    let (s, offset) = trim(s, offset);
+    if s.is_empty() {
+        return Ok(T::identity());
+    }
+    match s.strip_prefix('-') {
+        Some(rest) if rest.trim().is_empty() => Ok(-T::identity()),
+        // ```-3```のようにそのまま読めるならそれで、読めなければ```-```を外して読んでから符号を変える
+        Some(rest) => T::from_str(s).or_else(|_| {
+            let (rest, o) = trim(rest, offset + 1);
+            T::from_str(rest).map(|t| -t).map_err(|e| e.shift(o))
+        }),
+        None => T::from_str(s).map_err(|e| e.shift(offset)),
+    }
+}
@@ -1,7 +1,22 @@
+use crate::algebraic_structures::{
+    AdditiveGroup, CommutativeRing, EuclideanDomain, Field, IntegralDomain, Ring,
+};
 use crate::characteristic::Characteristic;
+use crate::format::{Notation, PolynomialFormat};
 use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::matrix::Matrix;
+use crate::parse::{
+    parse_coefficient, parse_integer, split_terms, strip_variable, trim, ParseError, ParseErrorKind,
+};
+use crate::power_series::reciprocal;
 use std::fmt;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::iter::FromIterator;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
 
 /// （1変数の）多項式
 ///
@@ -26,6 +41,16 @@ impl<T> Polynomial<T> {
             degree: 0,
         }
     }
+
+    /// 最高次係数。0のときは0を返す。
+    pub fn leading_coefficient(&self) -> &T {
+        &self.coefficients[self.degree]
+    }
+
+    /// 係数の配列（0次から順に、長さは次数+1）
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
 }
 
 impl<T: Zero + Eq> Polynomial<T> {
@@ -37,281 +62,638 @@ impl<T: Zero + Eq> Polynomial<T> {
             Some(self.degree)
         }
     }
+
+    /// 係数のベクトルの所有権を受け取って多項式を作る。最高次係数がnon-zeroになるよう末尾の0を取り除く。
+    fn from_vec(mut v: Vec<T>) -> Self {
+        while v.len() > 1 && v.last() == Some(&T::zero()) {
+            v.pop();
+        }
+        if v.is_empty() {
+            v.push(T::zero());
+        }
+        let d = v.len() - 1;
+        Self {
+            coefficients: v,
+            degree: d,
+        }
+    }
 }
 
-impl<T: Zero + Eq + Copy> Polynomial<T> {
+impl<T: Zero + Eq + Clone> Polynomial<T> {
     /// ```T```型のデータからなるベクトルを受け取り、それを係数としてもつ多項式を作る。
     /// 最高次係数がnon-zeroになるようにするのでTには```Zero```や```Eq```を要求。
-    pub fn new(v: &Vec<T>) -> Self {
-        let mut f: Vec<T> = Vec::new();
-        if v.len() == 0 {
-            f.push(T::zero());
-            Self {
-                coefficients: f,
-                degree: 0,
-            }
-        } else {
-            f.push(v[0]);
-            for &t in &v[1..] {
-                f.push(t.clone());
-            }
-            while f.len() > 1 {
-                if let Some(&t) = f.last() {
-                    if t == T::zero() {
-                        f.pop();
-                    } else {
-                        break;
-                    }
-                }
-            }
-            let d = f.len() - 1;
-            Self {
-                coefficients: f,
-                degree: d,
-            }
-        }
+    pub fn new(v: &[T]) -> Self {
+        Polynomial::from_vec(v.to_vec())
+    }
+
+    /// ```i```次の係数。次数より大きい```i```に対しては0を返す。
+    pub fn coeff(&self, i: usize) -> T {
+        self.coefficients.get(i).cloned().unwrap_or_else(T::zero)
+    }
+
+    /// 0でない項を（次数, 係数）の組として次数の低い順に返すイテレータ。
+    pub fn terms(&self) -> impl DoubleEndedIterator<Item = (usize, &T)> {
+        self.coefficients
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c != T::zero())
+    }
+
+    /// 単項式 c x^n
+    pub fn monomial(c: T, n: usize) -> Self {
+        let mut v = vec![T::zero(); n];
+        v.push(c);
+        Polynomial::from_vec(v)
     }
 }
 
-impl<T: Zero + Identity + Mul<Output = T> + AddAssign + Copy + Eq> Polynomial<T> {
+impl<T: Ring> Polynomial<T> {
+    /// 不定元 x そのもの。
+    pub fn x() -> Self {
+        Polynomial::monomial(T::identity(), 1)
+    }
+
+    /// 根を並べた配列から (x - r_1)(x - r_2)...(x - r_n) を作る。重根は同じ値を重複して並べる。
+    pub fn from_roots(roots: &[T]) -> Self {
+        roots.iter().fold(Self::identity(), |f, r| {
+            f * Polynomial::from_vec(vec![-r.clone(), T::identity()])
+        })
+    }
+
     /// 微分（derivative）を求める関数。
     pub fn derivative(f: &Self) -> Self {
         let mut integer = T::identity(); // 1,2,3,...に相当する元を作るために用意
         let mut v = Vec::<T>::new();
-        for &c in &f.coefficients[1..] {
-            v.push(c * integer);
+        for c in &f.coefficients[1..] {
+            v.push(c.clone() * integer.clone());
             integer += T::identity();
         }
-        Polynomial::new(&v)
+        Polynomial::from_vec(v)
     }
-}
 
-impl<T: Zero + Identity + Mul<Output = T> + MulAssign + AddAssign + Copy + Eq> Polynomial<T> {
-    /// 多項式に代入する。
+    /// 多項式に代入する。ホーナー法で計算する。
     pub fn evaluate(f: &Self, t: T) -> T {
-        let mut t_pow = T::identity();
         let mut ans = T::zero();
-        for &c in &f.coefficients {
-            ans += c * t_pow;
-            t_pow *= t;
+        for c in f.coefficients.iter().rev() {
+            ans = ans * t.clone() + c.clone();
         }
         ans
     }
 }
 
-/// ```println!```などで見やすく表示させるため、```Display```トレイトを実装。
-impl<T: fmt::Display + Zero + Identity + Eq> fmt::Display for Polynomial<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut s: String = String::new();
-        let mut flag = false;
-        if self.degree == 0 || self.coefficients[0] != T::zero() {
-            s.push_str(&self.coefficients[0].to_string())
-        } else {
-            flag = true;
+impl<T: Ring> Polynomial<T> {
+    /// 合成 f(g(x)) を求める。ホーナー法で計算する。
+    pub fn compose(f: &Self, g: &Self) -> Self {
+        let mut ans = Polynomial::zero();
+        for c in f.coefficients.iter().rev() {
+            ans = ans * g.clone() + Polynomial::new_constant(c.clone());
         }
-        if self.degree > 0 && self.coefficients[1] != T::zero() {
-            if !flag {
-                s.push_str(&" + ");
-            } else {
-                flag = false;
-            }
-            if self.coefficients[1] != T::identity() {
-                s.push_str(&self.coefficients[1].to_string());
-            }
-            s.push_str(&"x");
+        ans
+    }
+
+    /// 平行移動 f(x + a) を求める。
+    pub fn shift(f: &Self, a: T) -> Self {
+        Polynomial::compose(f, &Polynomial::from_vec(vec![a, T::identity()]))
+    }
+
+    /// f(cx) を求める。i次の係数に c^i を掛ける。
+    pub fn scale(f: &Self, c: T) -> Self {
+        let mut c_pow = T::identity();
+        let mut v = Vec::with_capacity(f.coefficients.len());
+        for a in &f.coefficients {
+            v.push(a.clone() * c_pow.clone());
+            c_pow *= c.clone();
         }
-        if self.degree > 1 {
-            for i in 2..=self.degree {
-                if self.coefficients[i] == T::zero() {
-                    continue;
-                }
-                if !flag {
-                    s.push_str(&" + ");
-                } else {
-                    flag = false;
-                }
-                if self.coefficients[i] != T::identity() {
-                    s.push_str(&self.coefficients[i].to_string());
-                }
-                s.push_str(&"x^");
-                s.push_str(&i.to_string());
+        Polynomial::from_vec(v)
+    }
+
+    /// 係数を逆順に並べたもの x^(deg f) f(1/x) を求める。
+    pub fn reverse(f: &Self) -> Self {
+        Polynomial::from_vec(f.coefficients.iter().rev().cloned().collect())
+    }
+
+    /// 点```a```のまわりでのテイラー展開。f(x) = Σ c_i (x - a)^i となる```c_0, ..., c_(deg f)```を返す。
+    pub fn taylor_coefficients(f: &Self, a: T) -> Vec<T> {
+        let mut v = Polynomial::shift(f, a).coefficients;
+        v.resize(f.degree + 1, T::zero());
+        v
+    }
+
+    /// 正方行列```a```を代入した f(a) を求める。ホーナー法で計算する。正方行列でないときはpanicする。
+    pub fn evaluate_matrix(f: &Self, a: &Matrix<T>) -> Matrix<T> {
+        let n = a.rows();
+        if !a.is_square() {
+            panic!("cannot evaluate a polynomial at a non-square matrix");
+        }
+        let mut ans = Matrix::new(n, n);
+        for c in f.coefficients.iter().rev() {
+            ans *= a.clone();
+            for i in 0..n {
+                ans[(i, i)] += c.clone();
             }
         }
-        write!(f, "{}", s)
+        ans
     }
 }
 
-/// 足し算の実装
-impl<T: Copy + Add<Output = T> + Zero + Eq> Add for Polynomial<T> {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self {
-        let mut v: Vec<T> = Vec::new();
-        v.push(self.coefficients[0] + rhs.coefficients[0]);
-        if self.degree < rhs.degree {
-            for i in 1..=self.degree {
-                v.push(self.coefficients[i] + rhs.coefficients[i]);
+impl<T: Ring + Inverse> Polynomial<T> {
+    /// 余り付きの割り算。```(q, r)```で、```f = q * g + r```かつ```r```の次数が```g```の次数より小さいもの。
+    ///
+    /// ```g```の最高次係数が逆元を持つ必要がある。```g```が0のときや最高次係数が可逆でないときはpanicする。
+    pub fn div_rem(f: &Self, g: &Self) -> (Self, Self) {
+        let lc_inv = match g.strict_deg() {
+            None => panic!("division by zero polynomial"),
+            Some(d) => g.coefficients[d]
+                .clone()
+                .inverse()
+                .expect("leading coefficient of the divisor is not invertible"),
+        };
+        let m = g.degree;
+        if f.degree < m || f.strict_deg().is_none() {
+            return (Polynomial::zero(), f.clone());
+        }
+        let mut r = f.coefficients.clone();
+        let mut q = vec![T::zero(); f.degree - m + 1];
+        for i in (0..q.len()).rev() {
+            let c = r[i + m].clone() * lc_inv.clone();
+            if c == T::zero() {
+                continue;
             }
-            for i in (self.degree + 1)..=rhs.degree {
-                v.push(rhs.coefficients[i]);
+            for (j, b) in g.coefficients.iter().enumerate() {
+                r[i + j] -= c.clone() * b.clone();
             }
-        } else if self.degree > rhs.degree {
-            for i in 1..=rhs.degree {
-                v.push(self.coefficients[i] + rhs.coefficients[i]);
+            q[i] = c;
+        }
+        r.truncate(m);
+        (Polynomial::from_vec(q), Polynomial::from_vec(r))
+    }
+
+    /// ```f^n mod m```を繰り返し二乗法で計算する。```m```の最高次係数が可逆でないときはpanicする。
+    pub fn pow_mod(f: &Self, n: u64, m: &Self) -> Self {
+        let mut ans = Polynomial::div_rem(&Polynomial::identity(), m).1;
+        let mut a = Polynomial::div_rem(f, m).1;
+        let mut k = n;
+        while k != 0 {
+            if k & 1 == 1 {
+                ans = Polynomial::div_rem(&(ans * a.clone()), m).1;
             }
-            for i in (rhs.degree + 1)..=self.degree {
-                v.push(self.coefficients[i]);
+            a = Polynomial::div_rem(&(a.clone() * a), m).1;
+            k >>= 1;
+        }
+        ans
+    }
+}
+
+impl<T: CommutativeRing> Polynomial<T> {
+    /// 擬除算（pseudo-division）。```g```の最高次係数をlcとして、
+    /// ```lc^(deg f - deg g + 1) * f = q * g + r```かつ```r```の次数が```g```の次数より小さい```(q, r)```を返す。
+    ///
+    /// 逆元を使わないので```Integer```係数などでも使える。```f```の次数が```g```の次数より小さいときは```(0, f)```を返す。
+    /// ```g```が0のときはpanicする。
+    pub fn pseudo_div_rem(f: &Self, g: &Self) -> (Self, Self) {
+        if g.strict_deg().is_none() {
+            panic!("division by zero polynomial");
+        }
+        let m = g.degree;
+        if f.degree < m || f.strict_deg().is_none() {
+            return (Polynomial::zero(), f.clone());
+        }
+        let lc = g.coefficients[m].clone();
+        let mut r = f.coefficients.clone();
+        let mut q = vec![T::zero(); f.degree - m + 1];
+        for i in (0..q.len()).rev() {
+            let c = r[i + m].clone();
+            for a in q.iter_mut().chain(r.iter_mut()) {
+                *a *= lc.clone();
             }
-        } else {
-            for i in 1..=self.degree {
-                v.push(self.coefficients[i] + rhs.coefficients[i]);
+            for (j, b) in g.coefficients.iter().enumerate() {
+                r[i + j] -= c.clone() * b.clone();
             }
+            q[i] = c;
         }
-        Polynomial::new(&v)
+        r.truncate(m);
+        (Polynomial::from_vec(q), Polynomial::from_vec(r))
     }
 }
 
-impl<T: Copy + Add<Output = T> + Zero + Eq> AddAssign for Polynomial<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        let mut v: Vec<T> = Vec::new();
-        v.push(self.coefficients[0] + rhs.coefficients[0]);
-        if self.degree < rhs.degree {
-            for i in 1..=self.degree {
-                v.push(self.coefficients[i] + rhs.coefficients[i]);
+impl<T: Field> Polynomial<T> {
+    /// 最高次係数で割ってmonicにしたものを返す。0はそのまま。
+    pub fn monic(&self) -> Self {
+        match self.leading_coefficient().clone().inverse() {
+            None => self.clone(),
+            Some(inv) => Polynomial::from_vec(
+                self.coefficients
+                    .iter()
+                    .map(|c| c.clone() * inv.clone())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// 最大公約数。monicなものを返す（```f```, ```g```がともに0のときは0）。
+    pub fn gcd(f: &Self, g: &Self) -> Self {
+        <Self as EuclideanDomain>::gcd(f, g).monic()
+    }
+
+    /// 拡張ユークリッドの互除法。```(d, s, t)```で、```d```はmonicな最大公約数、```s * f + t * g = d```となるもの。
+    pub fn extended_gcd(f: &Self, g: &Self) -> (Self, Self, Self) {
+        let (d, s, t) = <Self as EuclideanDomain>::extended_gcd(f, g);
+        match d.coefficients[d.degree].clone().inverse() {
+            None => (d, s, t),
+            Some(inv) => {
+                let c = Polynomial::new_constant(inv);
+                (d * c.clone(), s * c.clone(), t * c)
             }
-            for i in (self.degree + 1)..=rhs.degree {
-                v.push(rhs.coefficients[i]);
+        }
+    }
+
+    /// 最小公倍数。monicなものを返す（どちらかが0のときは0）。
+    pub fn lcm(f: &Self, g: &Self) -> Self {
+        if f.strict_deg().is_none() || g.strict_deg().is_none() {
+            return Polynomial::zero();
+        }
+        let d = Polynomial::gcd(f, g);
+        (Polynomial::div_rem(f, &d).0 * g.clone()).monic()
+    }
+}
+
+/// 点の個数がこれより多いときは、部分積の木を使う高速な多点評価・補間を使う
+const SUBPRODUCT_TREE_THRESHOLD: usize = 64;
+
+impl<T: Field> Polynomial<T> {
+    /// ```div_rem```と同じ結果を返すが、次数が大きいときは x^k での逆数をニュートン法で求めて商を計算する。
+    fn div_rem_fast(f: &Self, g: &Self) -> (Self, Self) {
+        let (n, m) = (f.degree, g.degree);
+        if n < m || m < SUBPRODUCT_TREE_THRESHOLD || n - m < SUBPRODUCT_TREE_THRESHOLD {
+            return Polynomial::div_rem(f, g);
+        }
+        // rev(f) = rev(q) rev(g) mod x^(n-m+1) から rev(q) を求める
+        let k = n - m + 1;
+        let rev_f: Vec<T> = f.coefficients.iter().rev().take(k).cloned().collect();
+        let rev_g: Vec<T> = g.coefficients.iter().rev().cloned().collect();
+        let mut rev_q = T::convolution(&rev_f, &reciprocal(&rev_g, k).unwrap());
+        rev_q.truncate(k);
+        rev_q.reverse();
+        let q = Polynomial::from_vec(rev_q);
+        let mut r = (f.clone() - q.clone() * g.clone()).coefficients;
+        r.truncate(m);
+        (q, Polynomial::from_vec(r))
+    }
+
+    /// 部分積の木。0段目は x - x_i たちで、1つ上の段は隣どうしを掛けたもの（余ったものはそのまま）。
+    fn subproduct_tree(xs: &[T]) -> Vec<Vec<Self>> {
+        let leaves: Vec<Self> = xs
+            .iter()
+            .map(|x| Polynomial::from_vec(vec![-x.clone(), T::identity()]))
+            .collect();
+        let mut tree = vec![leaves];
+        while tree.last().unwrap().len() > 1 {
+            let next = tree
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|c| match c {
+                    [a, b] => a.clone() * b.clone(),
+                    _ => c[0].clone(),
+                })
+                .collect();
+            tree.push(next);
+        }
+        tree
+    }
+
+    /// 多点評価。```xs```のそれぞれの点での値を返す。
+    ///
+    /// 点が多いときは部分積の木を上から順にたどって余りを取っていく。
+    pub fn multipoint_evaluate(f: &Self, xs: &[T]) -> Vec<T> {
+        if xs.len() <= SUBPRODUCT_TREE_THRESHOLD {
+            return xs
+                .iter()
+                .map(|x| Polynomial::evaluate(f, x.clone()))
+                .collect();
+        }
+        let tree = Polynomial::subproduct_tree(xs);
+        Polynomial::evaluate_down_tree(f, &tree)
+    }
+
+    /// 部分積の木を上から順にたどって、各葉 x - x_i での余り（つまり値）を求める。
+    fn evaluate_down_tree(f: &Self, tree: &[Vec<Self>]) -> Vec<T> {
+        let mut rems = vec![Polynomial::div_rem_fast(f, &tree[tree.len() - 1][0]).1];
+        for level in tree.iter().rev().skip(1) {
+            rems = level
+                .iter()
+                .enumerate()
+                .map(|(i, g)| Polynomial::div_rem_fast(&rems[i / 2], g).1)
+                .collect();
+        }
+        rems.into_iter()
+            .map(|r| r.coefficients[0].clone())
+            .collect()
+    }
+
+    /// 補間。```x```座標が相異なる点```(x_i, y_i)```たちを受け取り、f(x_i) = y_i となる次数が点の個数未満の多項式を返す。
+    ///
+    /// 点が少ないときはニュートンの差分商で、多いときは部分積の木を使ったラグランジュ補間で計算する。
+    /// ```x```座標に重複があるときはpanicする。
+    pub fn interpolate(points: &[(T, T)]) -> Self {
+        if points.len() <= SUBPRODUCT_TREE_THRESHOLD {
+            Polynomial::newton_interpolate(points)
+        } else {
+            Polynomial::fast_interpolate(points)
+        }
+    }
+
+    /// ニュートンの差分商による補間。O(n^2)
+    fn newton_interpolate(points: &[(T, T)]) -> Self {
+        let n = points.len();
+        let mut c: Vec<T> = points.iter().map(|(_, y)| y.clone()).collect();
+        for j in 1..n {
+            for i in (j..n).rev() {
+                let d = (points[i].0.clone() - points[i - j].0.clone())
+                    .inverse()
+                    .expect("x coordinates must be distinct");
+                c[i] = (c[i].clone() - c[i - 1].clone()) * d;
             }
-        } else if self.degree > rhs.degree {
-            for i in 1..=rhs.degree {
-                v.push(self.coefficients[i] + rhs.coefficients[i]);
+        }
+        let mut f = Polynomial::zero();
+        for i in (0..n).rev() {
+            let linear = Polynomial::from_vec(vec![-points[i].0.clone(), T::identity()]);
+            f = f * linear + Polynomial::new_constant(c[i].clone());
+        }
+        f
+    }
+
+    /// 部分積の木を使った補間。
+    /// M(x) = Π (x - x_i) として、f = Σ y_i / M'(x_i) * M(x) / (x - x_i) を木の下から順に組み立てる。
+    fn fast_interpolate(points: &[(T, T)]) -> Self {
+        let xs: Vec<T> = points.iter().map(|(x, _)| x.clone()).collect();
+        let tree = Polynomial::subproduct_tree(&xs);
+        let dm = Polynomial::derivative(&tree[tree.len() - 1][0]);
+        let mut nodes: Vec<Self> = Polynomial::evaluate_down_tree(&dm, &tree)
+            .into_iter()
+            .zip(points)
+            .map(|(d, (_, y))| {
+                let w = d.inverse().expect("x coordinates must be distinct");
+                Polynomial::new_constant(y.clone() * w)
+            })
+            .collect();
+        for level in tree.iter().take(tree.len() - 1) {
+            nodes = nodes
+                .chunks(2)
+                .zip(level.chunks(2))
+                .map(|(r, m)| match (r, m) {
+                    ([r0, r1], [m0, m1]) => r0.clone() * m1.clone() + r1.clone() * m0.clone(),
+                    _ => r[0].clone(),
+                })
+                .collect();
+        }
+        nodes.pop().unwrap()
+    }
+}
+
+impl<T: Field + Characteristic> Polynomial<T> {
+    /// 無平方分解（square-free decomposition）。
+    ///
+    /// ```f```をmonicにしたものを、互いに素で無平方なmonic多項式```g_i```と相異なる重複度```m_i```を用いて
+    /// Π g_i^(m_i) と表し、```(g_i, m_i)```を重複度の小さい順に並べて返す。定数の場合は空になる。
+    ///
+    /// 標数pのときは f' = 0 となることがあり、その場合は f(x) = g(x^p) = (g~(x))^p と見てp乗根を取る。
+    /// 係数のp乗根は係数自身とする（つまり係数体は素体 F_p を想定）。
+    pub fn square_free_decomposition(f: &Self) -> Vec<(Self, usize)> {
+        let mut factors: Vec<(Self, usize)> = Vec::new();
+        if f.degree == 0 {
+            return factors;
+        }
+        let one = Polynomial::identity();
+        let p = T::characteristic() as usize;
+        let f = f.monic();
+        let df = Polynomial::derivative(&f);
+        let mut c = if df.strict_deg().is_none() {
+            f
+        } else {
+            let mut c = Polynomial::gcd(&f, &df);
+            let mut w = Polynomial::div_rem(&f, &c).0;
+            let mut i = 1;
+            while w != one {
+                let y = Polynomial::gcd(&w, &c);
+                let z = Polynomial::div_rem(&w, &y).0;
+                if z != one {
+                    factors.push((z, i));
+                }
+                i += 1;
+                c = Polynomial::div_rem(&c, &y).0;
+                w = y;
             }
-            for i in (rhs.degree + 1)..=self.degree {
-                v.push(self.coefficients[i]);
+            c
+        };
+        if c != one && p != 0 {
+            c = Polynomial::pth_root(&c, p);
+            for (g, m) in Polynomial::square_free_decomposition(&c) {
+                factors.push((g, m * p));
             }
+        }
+        factors.sort_by_key(|(_, m)| *m);
+        factors
+    }
+
+    /// x^p の多項式になっている```f```のp乗根を取る。係数のp乗根は係数自身とする。
+    fn pth_root(f: &Self, p: usize) -> Self {
+        Polynomial::from_vec(f.coefficients.iter().step_by(p).cloned().collect())
+    }
+}
+
+/// ```println!```などで見やすく表示させるため、```Display```トレイトを実装。
+impl<T: fmt::Display + Ring> fmt::Display for Polynomial<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", PolynomialFormat::new().polynomial(self))
+    }
+}
+
+/// 係数を```T```の```to_latex()```で書き、```3x^{2} - \\frac{1}{2}x```のようにする。
+impl<T: Ring + ToLatex> ToLatex for Polynomial<T> {
+    fn to_latex(&self) -> String {
+        PolynomialFormat::new()
+            .notation(Notation::Latex)
+            .polynomial_with(self, T::to_latex)
+    }
+}
+
+/// 足し算の実装
+impl<T: AdditiveGroup> Add for Polynomial<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let (mut v, w) = if self.degree >= rhs.degree {
+            (self.coefficients, rhs.coefficients)
         } else {
-            for i in 1..=self.degree {
-                v.push(self.coefficients[i] + rhs.coefficients[i]);
-            }
+            (rhs.coefficients, self.coefficients)
+        };
+        for (a, b) in v.iter_mut().zip(w) {
+            *a += b;
         }
-        *self = Polynomial::new(&v)
+        Polynomial::from_vec(v)
+    }
+}
+
+impl<T: AdditiveGroup> AddAssign for Polynomial<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        let lhs = std::mem::replace(self, Polynomial::zero());
+        *self = lhs + rhs
     }
 }
 
 /// 引き算の実装
-impl<T: Copy + Sub<Output = T> + Zero + Eq> Sub for Polynomial<T> {
+impl<T: AdditiveGroup> Sub for Polynomial<T> {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
-        let mut v: Vec<T> = Vec::new();
-        v.push(self.coefficients[0] - rhs.coefficients[0]);
-        if self.degree < rhs.degree {
-            for i in 1..=self.degree {
-                v.push(self.coefficients[i] - rhs.coefficients[i]);
-            }
-            for i in (self.degree + 1)..=rhs.degree {
-                v.push(T::zero() - rhs.coefficients[i]);
-            }
-        } else if self.degree > rhs.degree {
-            for i in 1..=rhs.degree {
-                v.push(self.coefficients[i] - rhs.coefficients[i]);
-            }
-            for i in (rhs.degree + 1)..=self.degree {
-                v.push(self.coefficients[i]);
-            }
-        } else {
-            for i in 1..=self.degree {
-                v.push(self.coefficients[i] - rhs.coefficients[i]);
-            }
-        }
-        Polynomial::new(&v)
+        self + (-rhs)
     }
 }
 
-impl<T: Copy + Sub<Output = T> + Zero + Eq> SubAssign for Polynomial<T> {
+impl<T: AdditiveGroup> SubAssign for Polynomial<T> {
     fn sub_assign(&mut self, rhs: Self) {
-        let mut v: Vec<T> = Vec::new();
-        v.push(self.coefficients[0] - rhs.coefficients[0]);
-        if self.degree < rhs.degree {
-            for i in 1..=self.degree {
-                v.push(self.coefficients[i] - rhs.coefficients[i]);
-            }
-            for i in (self.degree + 1)..=rhs.degree {
-                v.push(T::zero() - rhs.coefficients[i]);
-            }
-        } else if self.degree > rhs.degree {
-            for i in 1..=rhs.degree {
-                v.push(self.coefficients[i] - rhs.coefficients[i]);
-            }
-            for i in (rhs.degree + 1)..=self.degree {
-                v.push(self.coefficients[i]);
-            }
-        } else {
-            for i in 1..=self.degree {
-                v.push(self.coefficients[i] - rhs.coefficients[i]);
-            }
-        }
-        *self = Polynomial::new(&v)
+        let lhs = std::mem::replace(self, Polynomial::zero());
+        *self = lhs - rhs
     }
 }
 
-/// 掛け算の実装
-impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Eq> Mul for Polynomial<T> {
+/// 掛け算の実装。係数環の```Ring::convolution```を使うので、```ModInt```係数ならNTTで計算される。
+impl<T: Ring> Mul for Polynomial<T> {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self {
-        let mut v: Vec<T> = vec![T::zero(); self.degree * rhs.degree + 1];
-        for i in 0..=(self.degree * rhs.degree) {
-            for j in 0..=i {
-                if i - j <= rhs.degree && j <= self.degree {
-                    v[i] += self.coefficients[j] * rhs.coefficients[i - j];
-                }
-            }
-        }
-        Polynomial::new(&v)
+        Polynomial::from_vec(T::convolution(&self.coefficients, &rhs.coefficients))
     }
 }
 
-impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Eq> MulAssign
-    for Polynomial<T>
-{
+impl<T: Ring> MulAssign for Polynomial<T> {
     fn mul_assign(&mut self, rhs: Self) {
-        let mut v: Vec<T> = vec![T::zero(); self.degree * rhs.degree + 1];
-        for i in 0..=(self.degree * rhs.degree) {
-            for j in 0..=i {
-                if i - j <= rhs.degree && j <= self.degree {
-                    v[i] += self.coefficients[j] * rhs.coefficients[i - j];
-                }
-            }
-        }
-        *self = Polynomial::new(&v)
+        let lhs = std::mem::replace(self, Polynomial::zero());
+        *self = lhs * rhs
+    }
+}
+
+/// 割り算（商）の実装。割る多項式の最高次係数が可逆でないときはpanicする。
+impl<T: Ring + Inverse> Div for Polynomial<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Polynomial::div_rem(&self, &rhs).0
+    }
+}
+
+impl<T: Ring + Inverse> DivAssign for Polynomial<T> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = Polynomial::div_rem(self, &rhs).0
+    }
+}
+
+/// 余りの実装。割る多項式の最高次係数が可逆でないときはpanicする。
+impl<T: Ring + Inverse> Rem for Polynomial<T> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        Polynomial::div_rem(&self, &rhs).1
+    }
+}
+
+impl<T: Ring + Inverse> RemAssign for Polynomial<T> {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = Polynomial::div_rem(self, &rhs).1
     }
 }
 
 /// unary negation の実装
-impl<T: Zero + Eq + Copy + Neg<Output = T>> Neg for Polynomial<T> {
+impl<T: AdditiveGroup> Neg for Polynomial<T> {
     type Output = Self;
     fn neg(self) -> Self {
+        let v: Vec<T> = self.coefficients.into_iter().map(|c| -c).collect();
+        Polynomial::from_vec(v)
+    }
+}
+
+/// ```2 + 3x + 6x^2 + 8x^4```（```Display```の形式）や```x^3 - 2x + 1```のような文字列を読む。
+/// 項の順番は自由で、同じ次数の項が複数あれば足し合わせる。係数は```T```の```FromStr```で読むので、
+/// ```[3]x^2```や```(-7)x```、```(1) / (2)x```のように係数の表示をそのまま前に付ければよい。
+/// ```Complex```のように係数の表示に```+```が含まれるときは```(3 + 4i)x```のように括弧でくくる（```Display```もそう書く）。
+impl<T: Ring + FromStr<Err = ParseError>> FromStr for Polynomial<T> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Polynomial::from_terms(&split_terms(s, 0)?)
+    }
+}
+
+impl<T: Ring + FromStr<Err = ParseError>> Polynomial<T> {
+    /// ```split_terms()```で分けた項を読んで足し合わせる。冪級数の読み取りでも使う。
+    pub(crate) fn from_terms(terms: &[(bool, &str, usize)]) -> Result<Self, ParseError> {
         let mut v: Vec<T> = Vec::new();
-        for c in self.coefficients.clone() {
-            v.push(-c);
+        for &(negative, t, o) in terms {
+            let (c, n) = match split_power(t, o)? {
+                Some((c, n)) => (parse_coefficient::<T>(c, o)?, n),
+                None => (T::from_str(t).map_err(|e| e.shift(o))?, 0),
+            };
+            if v.len() <= n {
+                v.resize(n + 1, T::zero());
+            }
+            if negative {
+                v[n] -= c;
+            } else {
+                v[n] += c;
+            }
+        }
+        Ok(Polynomial::from_vec(v))
+    }
+}
+
+/// 項を係数の部分と次数に分ける。```x```を含まない（定数項の）ときは```None```。
+fn split_power(t: &str, offset: usize) -> Result<Option<(&str, usize)>, ParseError> {
+    if let Some(k) = t.rfind('^') {
+        if let Some(c) = strip_variable(t[..k].trim_end(), 'x') {
+            let (e, o) = trim(&t[k + 1..], offset + k + 1);
+            let n = parse_integer(e, o)?;
+            if n < 0 || n > usize::MAX as i128 {
+                return Err(ParseError::new(o, ParseErrorKind::InvalidNumber));
+            }
+            return Ok(Some((c, n as usize)));
         }
-        Polynomial::new(&v)
+    }
+    Ok(strip_variable(t, 'x').map(|c| (c, 1)))
+}
+
+/// ```i```次の係数への参照。次数より大きい```i```に対してはpanicする（0を返したいときは```coeff()```を使う）。
+impl<T> Index<usize> for Polynomial<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.coefficients[i]
+    }
+}
+
+/// 0次から順に係数を並べたイテレータから多項式を作る。
+impl<T: Zero + Eq> FromIterator<T> for Polynomial<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Polynomial::from_vec(iter.into_iter().collect())
+    }
+}
+
+/// 係数の配列（0次から順に、長さは次数+1）に戻す。
+impl<T> From<Polynomial<T>> for Vec<T> {
+    fn from(f: Polynomial<T>) -> Vec<T> {
+        f.coefficients
     }
 }
 
 /// ```Zero```の実装
-impl<T: Zero + Copy> Zero for Polynomial<T> {
+impl<T: Zero> Zero for Polynomial<T> {
     fn zero() -> Self {
         Self {
-            coefficients: vec![T::zero(); 1],
+            coefficients: vec![T::zero()],
             degree: 0,
         }
     }
 }
 
 /// ```Identity```の実装
-impl<T: Identity + Copy> Identity for Polynomial<T> {
+impl<T: Identity> Identity for Polynomial<T> {
     fn identity() -> Self {
         Self {
-            coefficients: vec![T::identity(); 1],
+            coefficients: vec![T::identity()],
             degree: 0,
         }
     }
@@ -322,3 +704,34 @@ impl<T: Characteristic> Characteristic for Polynomial<T> {
         T::characteristic()
     }
 }
+
+impl<T: AdditiveGroup> AdditiveGroup for Polynomial<T> {}
+
+impl<T: Ring> Ring for Polynomial<T> {}
+
+impl<T: CommutativeRing> CommutativeRing for Polynomial<T> {}
+
+/// 係数環が整域なら多項式環も整域。
+impl<T: IntegralDomain> IntegralDomain for Polynomial<T> {}
+
+/// 体上の多項式環はユークリッド整域。ユークリッド関数は「次数 + 1」（0に対しては0）とする。
+impl<T: Field> EuclideanDomain for Polynomial<T> {
+    fn norm(&self) -> u64 {
+        match self.strict_deg() {
+            None => 0,
+            Some(d) => d as u64 + 1,
+        }
+    }
+
+    fn div_rem(a: &Self, b: &Self) -> (Self, Self) {
+        Polynomial::div_rem(a, b)
+    }
+
+    /// 最高次係数の逆元（0に対しては1）
+    fn normalizing_unit(&self) -> Self {
+        match self.leading_coefficient().clone().inverse() {
+            Some(inv) => Polynomial::new_constant(inv),
+            None => Self::identity(),
+        }
+    }
+}
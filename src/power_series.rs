@@ -0,0 +1,433 @@
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, Field, Ring};
+use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::parse::{split_terms, ParseError};
+use crate::polynomial::Polynomial;
+use crate::square_root::SquareRoot;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// x^N で打ち切った形式的冪級数。R\[\[x\]\] / (x^N) の元。
+///
+/// coefficientsは係数を並べた長さ```N```の配列。i番目がi次の項に対応。
+/// 精度```N```を型に持たせることで、```Zero```や```Identity```も実装できるようにした。
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PowerSeries<T, const N: usize> {
+    coefficients: Vec<T>,
+}
+
+/// 整数 n に相当する元を、1を足し合わせて（二進法で）作る。
+fn from_u64<T: Ring>(n: u64) -> T {
+    let mut ans = T::zero();
+    let mut pow = T::identity();
+    let mut k = n;
+    while k != 0 {
+        if k & 1 == 1 {
+            ans += pow.clone();
+        }
+        pow = pow.clone() + pow;
+        k >>= 1;
+    }
+    ans
+}
+
+/// 畳み込みを長さ```n```で打ち切る。
+fn mul_truncated<T: Ring>(a: &[T], b: &[T], n: usize) -> Vec<T> {
+    let mut v = T::convolution(&a[..a.len().min(n)], &b[..b.len().min(n)]);
+    v.resize(n, T::zero());
+    v
+}
+
+/// ```a```の逆数を mod x^n でニュートン法により求める。定数項が可逆でなければ```None```。
+pub(crate) fn reciprocal<T: Field>(a: &[T], n: usize) -> Option<Vec<T>> {
+    let mut g = vec![a[0].clone().inverse()?];
+    let two = from_u64::<T>(2);
+    let mut m = 1;
+    while m < n {
+        m = (m * 2).min(n);
+        // g <- g (2 - a g) mod x^m
+        let mut h: Vec<T> = mul_truncated(a, &g, m).into_iter().map(|c| -c).collect();
+        h[0] += two.clone();
+        g = mul_truncated(&g, &h, m);
+    }
+    g.truncate(n);
+    Some(g)
+}
+
+/// 形式的な微分。長さは1つ減る。
+fn derivative<T: Ring>(a: &[T]) -> Vec<T> {
+    a.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| c.clone() * from_u64(i as u64))
+        .collect()
+}
+
+/// 形式的な積分（定数項は0）を長さ```n```で打ち切る。i次の係数を i で割るので、標数が0か```n```以上である必要がある。
+fn integral<T: Field>(a: &[T], n: usize) -> Vec<T> {
+    let mut v = vec![T::zero(); n];
+    for (i, c) in a.iter().enumerate().take(n.saturating_sub(1)) {
+        let inv = from_u64::<T>(i as u64 + 1)
+            .inverse()
+            .expect("characteristic is too small for this precision");
+        v[i + 1] = c.clone() * inv;
+    }
+    v
+}
+
+/// 1, 2, ..., n - 1 がすべて可逆かどうか。つまり標数が0か```n```以上で、```integral()```が長さ```n```で使えるかどうか。
+fn has_large_characteristic<T: Field>(n: usize) -> bool {
+    let mut i = T::zero();
+    for _ in 1..n {
+        i += T::identity();
+        if i == T::zero() {
+            return false;
+        }
+    }
+    true
+}
+
+/// 定数項が1の```a```の対数を mod x^n で求める。log a = ∫ a' / a
+fn log<T: Field>(a: &[T], n: usize) -> Vec<T> {
+    let d = mul_truncated(&derivative(a), &reciprocal(a, n).unwrap(), n);
+    integral(&d, n)
+}
+
+/// 定数項が0の```a```の指数関数を mod x^n でニュートン法により求める。
+fn exp<T: Field>(a: &[T], n: usize) -> Vec<T> {
+    let mut g = vec![T::identity()];
+    let mut m = 1;
+    while m < n {
+        // n を超えると log で n 以上の数で割ることになるので、n で止める
+        m = (m * 2).min(n);
+        // g <- g (1 - log g + a) mod x^m
+        g.resize(m, T::zero());
+        let mut h: Vec<T> = log(&g, m).into_iter().map(|c| -c).collect();
+        for (x, y) in h.iter_mut().zip(a) {
+            *x += y.clone();
+        }
+        h[0] += T::identity();
+        g = mul_truncated(&g, &h, m);
+    }
+    g.truncate(n);
+    g
+}
+
+impl<T: Ring, const N: usize> PowerSeries<T, N> {
+    /// 係数の配列から作る。```N```次以上の項は捨て、足りない分は0で埋める。
+    pub fn new(v: &[T]) -> Self {
+        let mut coefficients = v[..v.len().min(N)].to_vec();
+        coefficients.resize(N, T::zero());
+        Self { coefficients }
+    }
+
+    /// 多項式を冪級数とみなす。```N```次以上の項は捨てる。
+    pub fn from_polynomial(f: &Polynomial<T>) -> Self {
+        Self::new(f.coefficients())
+    }
+
+    /// ```N```次未満の部分を多項式として取り出す。
+    pub fn to_polynomial(&self) -> Polynomial<T> {
+        Polynomial::new(&self.coefficients)
+    }
+
+    /// 精度（x^N で打ち切っているときの```N```）
+    pub fn precision(&self) -> usize {
+        N
+    }
+
+    /// ```i```次の係数。```N```以上の```i```に対しては0を返す。
+    pub fn coeff(&self, i: usize) -> T {
+        self.coefficients.get(i).cloned().unwrap_or_else(T::zero)
+    }
+
+    /// 形式的な微分。（N-1）次の係数は分からないので0にする。
+    pub fn derivative(&self) -> Self {
+        PowerSeries::new(&derivative(&self.coefficients))
+    }
+
+    /// 合成 f(g(x))。```g```の定数項が0でなければ打ち切りで値が定まらないので```None```を返す。
+    pub fn compose(&self, g: &Self) -> Option<Self> {
+        if g.coefficients.first().is_some_and(|c| *c != T::zero()) {
+            return None;
+        }
+        // ホーナー法
+        let mut ans = vec![T::zero(); N];
+        for c in self.coefficients.iter().rev() {
+            ans = mul_truncated(&ans, &g.coefficients, N);
+            if let Some(a) = ans.first_mut() {
+                *a += c.clone();
+            }
+        }
+        Some(Self { coefficients: ans })
+    }
+}
+
+impl<T: Field, const N: usize> PowerSeries<T, N> {
+    /// 形式的な積分（定数項は0）。i次の係数を i で割るので、係数体の標数は```N```以上である必要がある。
+    /// そうでないときはpanicする。
+    pub fn integral(&self) -> Self {
+        Self {
+            coefficients: integral(&self.coefficients, N),
+        }
+    }
+
+    /// 対数 log f。定数項が1でないときや、係数体の標数が```N```より小さくて積分できないときは```None```を返す。
+    pub fn log(&self) -> Option<Self> {
+        if N == 0 {
+            return Some(self.clone());
+        }
+        if self.coefficients[0] != T::identity() || !has_large_characteristic::<T>(N) {
+            return None;
+        }
+        Some(Self {
+            coefficients: log(&self.coefficients, N),
+        })
+    }
+
+    /// 指数関数 exp f。定数項が0でないときや、係数体の標数が```N```より小さくて積分できないときは```None```を返す。
+    pub fn exp(&self) -> Option<Self> {
+        if N == 0 {
+            return Some(self.clone());
+        }
+        if self.coefficients[0] != T::zero() || !has_large_characteristic::<T>(N) {
+            return None;
+        }
+        Some(Self {
+            coefficients: exp(&self.coefficients, N),
+        })
+    }
+
+    /// べき乗 f^k。f = x^s c g（cは0でない定数、gの定数項は1）として x^(sk) c^k exp(k log g) で計算する。
+    ///
+    /// ```log```や```exp```は係数体の標数が```N```より小さいと使えないので、そのときは繰り返し二乗法で計算する。
+    pub fn pow(&self, k: u64) -> Self {
+        if k == 0 {
+            return Self::identity();
+        }
+        if !has_large_characteristic::<T>(N) {
+            let mut ans = Self::identity();
+            let mut base = self.clone();
+            let mut e = k;
+            while e != 0 {
+                if e & 1 == 1 {
+                    ans *= base.clone();
+                }
+                base = base.clone() * base;
+                e >>= 1;
+            }
+            return ans;
+        }
+        let s = match self.coefficients.iter().position(|c| *c != T::zero()) {
+            None => return self.clone(),
+            Some(s) => s,
+        };
+        if (s as u128) * (k as u128) >= N as u128 {
+            return Self::zero();
+        }
+        let shift = s * k as usize;
+        let n = N - shift;
+        let c = self.coefficients[s].clone();
+        let c_inv = c.clone().inverse().unwrap();
+        let g: Vec<T> = self.coefficients[s..]
+            .iter()
+            .map(|x| x.clone() * c_inv.clone())
+            .collect();
+        let kk = from_u64::<T>(k);
+        let lg: Vec<T> = log(&g[..n], n)
+            .into_iter()
+            .map(|x| x * kk.clone())
+            .collect();
+        let mut c_pow = T::identity();
+        let mut base = c;
+        let mut e = k;
+        while e != 0 {
+            if e & 1 == 1 {
+                c_pow *= base.clone();
+            }
+            base = base.clone() * base;
+            e >>= 1;
+        }
+        let mut v = vec![T::zero(); shift];
+        v.extend(exp(&lg, n).into_iter().map(|x| x * c_pow.clone()));
+        PowerSeries::new(&v)
+    }
+}
+
+impl<T: Field + SquareRoot, const N: usize> PowerSeries<T, N> {
+    /// 平方根。f = x^(2k) c g（cは0でない定数、gの定数項は1）の形のときに x^k √c √g を返す。
+    /// 0でない最低次の次数が奇数のときや、cが```T```の中に平方根を持たないときは```None```を返す。
+    /// √g はニュートン法 h <- (h + g / h) / 2 で求めるので、標数は2でない必要がある。
+    ///
+    /// gは x^(N-2k) までしか分からないので、k > 0 のときは上のk個の係数は0にしてしまう。
+    pub fn sqrt(&self) -> Option<Self> {
+        let s = match self.coefficients.iter().position(|c| *c != T::zero()) {
+            None => return Some(self.clone()),
+            Some(s) => s,
+        };
+        if s % 2 == 1 {
+            return None;
+        }
+        let c = self.coefficients[s].clone();
+        let root_c = c.clone().square_root()?;
+        let c_inv = c.inverse()?;
+        let g: Vec<T> = self.coefficients[s..]
+            .iter()
+            .map(|x| x.clone() * c_inv.clone())
+            .collect();
+        let n = g.len();
+        let half = from_u64::<T>(2).inverse()?;
+        let mut h = vec![T::identity()];
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            let q = mul_truncated(&g, &reciprocal(&h, m).unwrap(), m);
+            h.resize(m, T::zero());
+            h = h
+                .into_iter()
+                .zip(q)
+                .map(|(x, y)| (x + y) * half.clone())
+                .collect();
+        }
+        h.truncate(n);
+        let mut v = vec![T::zero(); s / 2];
+        v.extend(h.into_iter().map(|x| x * root_c.clone()));
+        Some(PowerSeries::new(&v))
+    }
+}
+
+/// ```println!```などで見やすく表示させるため、```Display```トレイトを実装。```2 + 3x + O(x^5)```のように表示する。
+impl<T: fmt::Display + Ring, const N: usize> fmt::Display for PowerSeries<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let p = self.to_polynomial();
+        if p.strict_deg().is_none() {
+            write!(f, "O(x^{})", N)
+        } else {
+            write!(f, "{} + O(x^{})", p, N)
+        }
+    }
+}
+
+/// ```1 + 2x + O(x^4)```（```Display```の形式）や```O(x^4)```、打ち切りの項のない```1 + 2x```を読む。
+/// ```N```次以上の項は捨てる。
+impl<T: Ring + FromStr<Err = ParseError>, const N: usize> FromStr for PowerSeries<T, N> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms: Vec<_> = split_terms(s, 0)?
+            .into_iter()
+            .filter(|&(negative, t, _)| negative || !t.starts_with("O("))
+            .collect();
+        Polynomial::from_terms(&terms).map(|f| Self::from_polynomial(&f))
+    }
+}
+
+impl<T: Ring, const N: usize> From<Polynomial<T>> for PowerSeries<T, N> {
+    fn from(f: Polynomial<T>) -> Self {
+        Self::from_polynomial(&f)
+    }
+}
+
+impl<T: AdditiveGroup, const N: usize> Add for PowerSeries<T, N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            coefficients: self
+                .coefficients
+                .into_iter()
+                .zip(rhs.coefficients)
+                .map(|(a, b)| a + b)
+                .collect(),
+        }
+    }
+}
+
+impl<T: AdditiveGroup, const N: usize> AddAssign for PowerSeries<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.coefficients.iter_mut().zip(rhs.coefficients) {
+            *a += b;
+        }
+    }
+}
+
+impl<T: AdditiveGroup, const N: usize> Sub for PowerSeries<T, N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            coefficients: self
+                .coefficients
+                .into_iter()
+                .zip(rhs.coefficients)
+                .map(|(a, b)| a - b)
+                .collect(),
+        }
+    }
+}
+
+impl<T: AdditiveGroup, const N: usize> SubAssign for PowerSeries<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.coefficients.iter_mut().zip(rhs.coefficients) {
+            *a -= b;
+        }
+    }
+}
+
+/// 掛け算の実装。係数環の```Ring::convolution```を使うので、```ModInt```係数ならNTTで計算される。
+impl<T: Ring, const N: usize> Mul for PowerSeries<T, N> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            coefficients: mul_truncated(&self.coefficients, &rhs.coefficients, N),
+        }
+    }
+}
+
+impl<T: Ring, const N: usize> MulAssign for PowerSeries<T, N> {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.coefficients = mul_truncated(&self.coefficients, &rhs.coefficients, N);
+    }
+}
+
+/// unary negation の実装
+impl<T: AdditiveGroup, const N: usize> Neg for PowerSeries<T, N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            coefficients: self.coefficients.into_iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+impl<T: Zero + Clone, const N: usize> Zero for PowerSeries<T, N> {
+    fn zero() -> Self {
+        Self {
+            coefficients: vec![T::zero(); N],
+        }
+    }
+}
+
+impl<T: Zero + Identity + Clone, const N: usize> Identity for PowerSeries<T, N> {
+    fn identity() -> Self {
+        let mut coefficients = vec![T::zero(); N];
+        if let Some(c) = coefficients.first_mut() {
+            *c = T::identity();
+        }
+        Self { coefficients }
+    }
+}
+
+/// 逆元の実装。定数項が可逆なら逆数をニュートン法で求め、そうでなければ```None```を返す。
+impl<T: Field, const N: usize> Inverse for PowerSeries<T, N> {
+    fn inverse(self) -> Option<Self> {
+        if N == 0 {
+            return Some(self);
+        }
+        reciprocal(&self.coefficients, N).map(|coefficients| Self { coefficients })
+    }
+}
+
+impl<T: AdditiveGroup, const N: usize> AdditiveGroup for PowerSeries<T, N> {}
+
+impl<T: Ring, const N: usize> Ring for PowerSeries<T, N> {}
+
+impl<T: CommutativeRing, const N: usize> CommutativeRing for PowerSeries<T, N> {}
@@ -1,7 +1,16 @@
+use crate::algebraic_structures::Ring;
 use crate::characteristic::Characteristic;
-use crate::identities::Zero;
+use crate::format::{Notation, PolynomialFormat};
+use crate::identities::{Identity, Zero};
+use crate::latex::ToLatex;
+use crate::parse::{
+    parse_coefficient, split_terms, strip_delimiters, strip_parentheses, strip_variable, trim,
+    ParseError, ParseErrorKind,
+};
+use crate::polynomial::Polynomial;
 use std::fmt;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// R\[x\]/(x^2 - bx - c) の元。
 /// ここでRは型```T```の対象のなす環。
@@ -21,19 +30,82 @@ impl<T> QuadExt<T> {
     /// コンストラクタ。1つめの引数が定数項。2つめが1次の項。3つめと4つめが割るイデアルの生成元の1次の係数と定数項。
     pub fn new(constant: T, first: T, b: T, c: T) -> Self {
         Self {
-            constant: constant,
-            first: first,
-            b: b,
-            c: c,
+            constant,
+            first,
+            b,
+            c,
         }
     }
 }
 
+impl<T> QuadExt<T>
+where
+    T: Copy + Zero + Identity + Add<Output = T> + Neg<Output = T> + FromStr<Err = ParseError>,
+{
+    /// ```[(2) + (3)x]```（```Display```の形式）や```[2 - 3x]```を、b, c を引数で渡して読む。
+    /// b, c も文字列に書いてあるときは```FromStr```を使う。
+    pub fn parse(s: &str, b: T, c: T) -> Result<Self, ParseError> {
+        let (t, o) = trim(s, 0);
+        let (t, o) = strip_delimiters(t, '[', ']', o).unwrap_or((t, o));
+        let mut constant = T::zero();
+        let mut first = T::zero();
+        for (negative, t, o) in split_terms(t, o)? {
+            let (part, value) = match strip_variable(t, 'x') {
+                Some(coefficient) => {
+                    let (coefficient, o) = strip_parentheses(coefficient, o);
+                    (&mut first, parse_coefficient::<T>(coefficient, o)?)
+                }
+                None => {
+                    let (t, o) = strip_parentheses(t, o);
+                    (&mut constant, T::from_str(t).map_err(|e| e.shift(o))?)
+                }
+            };
+            *part = *part + if negative { -value } else { value };
+        }
+        Ok(QuadExt::new(constant, first, b, c))
+    }
+}
+
 /// ```println!```などで見やすく表示させるため、```Display```トレイトを実装。
 /// 型```T```がそもそも```Display```トレイトを実装していることを要求。
+///
+/// ```{:#}```で表示すると、```[(2) + (3)x] mod x^2 - 1x - 3```のように割る多項式 x^2 - bx - c も書く（```FromStr```で読める）。
 impl<T: fmt::Display> fmt::Display for QuadExt<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "[({}) + ({})x]", self.constant, self.first)
+        write!(f, "[({}) + ({})x]", self.constant, self.first)?;
+        if f.alternate() {
+            write!(f, " mod x^2 - {}x - {}", self.b, self.c)?;
+        }
+        Ok(())
+    }
+}
+
+/// ```[(2) + (3)x] mod x^2 - 1x - 3```（```{:#}```で表示した形式）や```[2 - 3x] mod x^2 - 2```を読む。
+/// ```mod```の後ろは最高次係数が1の2次式 x^2 - bx - c でなければならない（そうでなければ```ParseErrorKind::InvalidModulus```）。
+/// ```mod```がないと b, c が決まらないので```ParseErrorKind::ModulusNotSet```を返す。
+impl<T: Ring + Copy + FromStr<Err = ParseError>> FromStr for QuadExt<T> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let i = s
+            .find("mod")
+            .ok_or_else(|| ParseError::new(s.len(), ParseErrorKind::ModulusNotSet))?;
+        let o = i + "mod".len();
+        let f = Polynomial::<T>::from_str(&s[o..]).map_err(|e| e.shift(o))?;
+        if f.strict_deg() != Some(2) || f.coeff(2) != T::identity() {
+            return Err(ParseError::new(o, ParseErrorKind::InvalidModulus));
+        }
+        QuadExt::parse(&s[..i], -f.coeff(1), -f.coeff(0))
+    }
+}
+
+/// x を```\\alpha```として ```1 + 2\\alpha``` のように書く（b, c は型からは分からないので```\\sqrt{}```は使わない）。
+impl<T: Ring + ToLatex> ToLatex for QuadExt<T> {
+    fn to_latex(&self) -> String {
+        let f = Polynomial::new(&[self.constant.clone(), self.first.clone()]);
+        PolynomialFormat::new()
+            .variable("\\alpha")
+            .notation(Notation::Latex)
+            .polynomial_with(&f, T::to_latex)
     }
 }
 
@@ -1,8 +1,18 @@
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, Field, IntegralDomain, Ring};
 use crate::characteristic::Characteristic;
+use crate::format::{Notation, PolynomialFormat};
 use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::parse::{
+    parse_coefficient, split_terms, strip_delimiters, strip_variable, trim, ParseError,
+};
+use crate::polynomial::Polynomial;
+use crate::quadratic_integer::generator_latex;
 use crate::rational_number::RationalNumber;
 use std::fmt;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// Q\[x\]/(x^2 - Bx - C) の元（B, Cは整数ｓ）
 ///
@@ -16,10 +26,7 @@ pub struct QuadField<const B: i64, const C: i64> {
 impl<const B: i64, const C: i64> QuadField<B, C> {
     /// コンストラクタ。1つめの引数が定数項。2つめが1次の項。
     pub fn new(constant: RationalNumber, first: RationalNumber) -> Self {
-        Self {
-            constant: constant,
-            first: first,
-        }
+        Self { constant, first }
     }
 }
 
@@ -30,6 +37,38 @@ impl<const B: i64, const C: i64> fmt::Display for QuadField<B, C> {
     }
 }
 
+/// ```[((1) / (2)) + ((-3) / (4))x]```（```Display```の形式）や```[1/2 - 3/4x]```を読む。
+impl<const B: i64, const C: i64> FromStr for QuadField<B, C> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (t, o) = trim(s, 0);
+        let (t, o) = strip_delimiters(t, '[', ']', o).unwrap_or((t, o));
+        let (mut constant, mut first) = (RationalNumber::zero(), RationalNumber::zero());
+        for (negative, t, o) in split_terms(t, o)? {
+            let (part, value) = match strip_variable(t, 'x') {
+                Some(c) => (&mut first, parse_coefficient::<RationalNumber>(c, o)?),
+                None => (
+                    &mut constant,
+                    RationalNumber::from_str(t).map_err(|e| e.shift(o))?,
+                ),
+            };
+            *part += if negative { -value } else { value };
+        }
+        Ok(QuadField::new(constant, first))
+    }
+}
+
+/// ```QuadInt```と同じく、x を```\\sqrt{C}```や```\\alpha```として ```\\frac{1}{2} + \\sqrt{3}``` のように書く。
+impl<const B: i64, const C: i64> ToLatex for QuadField<B, C> {
+    fn to_latex(&self) -> String {
+        let f = Polynomial::new(&[self.constant, self.first]);
+        PolynomialFormat::new()
+            .variable(&generator_latex(B, C))
+            .notation(Notation::Latex)
+            .polynomial_with(&f, RationalNumber::to_latex)
+    }
+}
+
 impl<const B: i64, const C: i64> Add for QuadField<B, C> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
@@ -126,3 +165,30 @@ impl<const B: i64, const C: i64> Characteristic for QuadField<B, C> {
         0
     }
 }
+
+/// 逆元の実装。xの共役は B - x なので、ノルム a^2 + abB - b^2C で割って求める。
+/// ノルムが0（x^2 - Bx - C が可約で零因子になっている場合）のときは```None```を返す。
+impl<const B: i64, const C: i64> Inverse for QuadField<B, C> {
+    fn inverse(self) -> Option<QuadField<B, C>> {
+        let b = RationalNumber::new(B, 1);
+        let c = RationalNumber::new(C, 1);
+        let norm = self.constant * self.constant + self.constant * self.first * b
+            - self.first * self.first * c;
+        norm.inverse().map(|n| Self {
+            constant: (self.constant + self.first * b) * n,
+            first: -self.first * n,
+        })
+    }
+}
+
+impl<const B: i64, const C: i64> AdditiveGroup for QuadField<B, C> {}
+
+impl<const B: i64, const C: i64> Ring for QuadField<B, C> {}
+
+impl<const B: i64, const C: i64> CommutativeRing for QuadField<B, C> {}
+
+/// x^2 - Bx - C が既約であることは使う側が保証する。
+impl<const B: i64, const C: i64> IntegralDomain for QuadField<B, C> {}
+
+/// x^2 - Bx - C が既約であることは使う側が保証する。
+impl<const B: i64, const C: i64> Field for QuadField<B, C> {}
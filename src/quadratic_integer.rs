@@ -1,6 +1,15 @@
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, Ring};
+use crate::format::{Notation, PolynomialFormat};
 use crate::identities::{Identity, Zero};
+use crate::integer::Integer;
+use crate::latex::ToLatex;
+use crate::parse::{
+    parse_coefficient, parse_i64, split_terms, strip_delimiters, strip_variable, trim, ParseError,
+};
+use crate::polynomial::Polynomial;
 use std::fmt;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// Z\[x\]/(x^2 - Bx - C) の元
 ///
@@ -14,10 +23,7 @@ pub struct QuadInt<const B: i64, const C: i64> {
 impl<const B: i64, const C: i64> QuadInt<B, C> {
     /// コンストラクタ。1つめの引数が定数項。2つめが1次の項。
     pub fn new(constant: i64, first: i64) -> Self {
-        Self {
-            constant: constant,
-            first: first,
-        }
+        Self { constant, first }
     }
 }
 
@@ -28,6 +34,44 @@ impl<const B: i64, const C: i64> fmt::Display for QuadInt<B, C> {
     }
 }
 
+/// ```[1 + 1x]```（```Display```の形式）や```[2 - 3x]```、括弧のない```2 - 3x```を読む。
+impl<const B: i64, const C: i64> FromStr for QuadInt<B, C> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (t, o) = trim(s, 0);
+        let (t, o) = strip_delimiters(t, '[', ']', o).unwrap_or((t, o));
+        let (mut constant, mut first) = (0, 0);
+        for (negative, t, o) in split_terms(t, o)? {
+            let (part, value) = match strip_variable(t, 'x') {
+                Some(c) => (&mut first, parse_coefficient::<Integer>(c, o)?.to_int()),
+                None => (&mut constant, parse_i64(t, o)?),
+            };
+            *part += if negative { -value } else { value };
+        }
+        Ok(QuadInt::new(constant, first))
+    }
+}
+
+/// x を、B = 0 のときは```\\sqrt{C}```（C = -1 なら```i```）、それ以外では```\\alpha```として ```1 + 2\\sqrt{3}``` のように書く。
+impl<const B: i64, const C: i64> ToLatex for QuadInt<B, C> {
+    fn to_latex(&self) -> String {
+        let f = Polynomial::new(&[Integer::new(self.constant), Integer::new(self.first)]);
+        PolynomialFormat::new()
+            .variable(&generator_latex(B, C))
+            .notation(Notation::Latex)
+            .polynomial_with(&f, Integer::to_latex)
+    }
+}
+
+/// x^2 - Bx - C の根 x を LaTeX でどう書くか
+pub(crate) fn generator_latex(b: i64, c: i64) -> String {
+    match (b, c) {
+        (0, -1) => "i".to_string(),
+        (0, _) => format!("\\sqrt{{{}}}", c),
+        _ => "\\alpha".to_string(),
+    }
+}
+
 impl<const B: i64, const C: i64> Add for QuadInt<B, C> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
@@ -116,3 +160,9 @@ impl<const B: i64, const C: i64> Neg for QuadInt<B, C> {
         }
     }
 }
+
+impl<const B: i64, const C: i64> AdditiveGroup for QuadInt<B, C> {}
+
+impl<const B: i64, const C: i64> Ring for QuadInt<B, C> {}
+
+impl<const B: i64, const C: i64> CommutativeRing for QuadInt<B, C> {}
@@ -1,8 +1,19 @@
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, Field, IntegralDomain, Ring};
 use crate::characteristic::Characteristic;
 use crate::identities::{Identity, Zero};
 use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::number_theory::factorize;
+use crate::parse::{parse_i64, parse_integer, strip_parentheses, trim, ParseError, ParseErrorKind};
+use crate::polynomial::Polynomial;
+use crate::roots::Roots;
+use crate::solution_set::SolutionSet;
+use crate::square_root::SquareRoot;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// 有理数（分数）を表現するための```RationalNumber```構造体の定義。
 ///
@@ -43,12 +54,152 @@ impl RationalNumber {
     }
 }
 
+/// 有理数係数の多項式の有理数の根を重複度込みで求める。0多項式に対しては空集合を返す。
+///
+/// 分母を払った整数係数の多項式の根 p/q は、p が0でない最低次の係数の、q が最高次係数の約数になることを使う。
+/// 約数は素因数分解（ポラード・ロー法）から作るので係数が大きくても列挙はすぐ終わるが、
+/// 候補の個数は2つの係数の約数の個数の積になるので、約数の多い係数どうしだと遅くなる。
+/// 分母を払った係数が```i64```に収まらないときはpanicする。
+pub fn rational_roots(f: &Polynomial<RationalNumber>) -> SolutionSet<RationalNumber> {
+    let mut s = SolutionSet::new(HashSet::new());
+    if f.strict_deg().is_none() {
+        return s;
+    }
+    let lcm = f.coefficients().iter().fold(1u64, |l, c| {
+        (l / num::Integer::gcd(&l, &c.denominator))
+            .checked_mul(c.denominator)
+            .expect("coefficients are too large to clear denominators")
+    });
+    let integers: Vec<i64> = f
+        .coefficients()
+        .iter()
+        .map(|c| {
+            i64::try_from(lcm / c.denominator)
+                .ok()
+                .and_then(|m| c.numerator.checked_mul(m))
+                .expect("coefficients are too large to clear denominators")
+        })
+        .collect();
+    let lowest = integers.iter().find(|&&a| a != 0).unwrap().unsigned_abs();
+    let highest = integers[f.deg()].unsigned_abs();
+    let mut candidates = vec![RationalNumber::new(0, 1)];
+    for p in divisors(lowest) {
+        for q in divisors(highest) {
+            candidates.push(RationalNumber::new(p as i64, q));
+            candidates.push(RationalNumber::new(-(p as i64), q));
+        }
+    }
+    let mut g = f.clone();
+    for r in candidates {
+        let linear = Polynomial::new(&[-r, RationalNumber::new(1, 1)]);
+        let mut m = 0;
+        loop {
+            let (q, rem) = Polynomial::div_rem(&g, &linear);
+            if rem.strict_deg().is_some() {
+                break;
+            }
+            g = q;
+            m += 1;
+        }
+        if m > 0 {
+            s.insert_with_multiplicity(r, m);
+        }
+    }
+    s
+}
+
+/// 正の約数全体。素因数分解から作る。
+fn divisors(n: u64) -> Vec<u64> {
+    let mut v = vec![1];
+    for (p, e) in factorize(n) {
+        let smaller = v.clone();
+        let mut q = 1;
+        for _ in 0..e {
+            q *= p;
+            v.extend(smaller.iter().map(|d| d * q));
+        }
+    }
+    v
+}
+
+/// 根は小さい順に並べる。
+impl Roots for RationalNumber {
+    fn roots(f: &Polynomial<RationalNumber>) -> Option<Vec<RationalNumber>> {
+        f.strict_deg()?;
+        let mut v: Vec<RationalNumber> = rational_roots(f).iter().map(|(r, _)| *r).collect();
+        v.sort_by(|a, b| a.to_int().total_cmp(&b.to_int()));
+        Some(v)
+    }
+}
+
 impl fmt::Display for RationalNumber {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "({}) / ({})", self.numerator, self.denominator)
     }
 }
 
+/// ```(-7) / (21)```（```Display```の形式）や```-7/21```、```5```を読む。
+impl FromStr for RationalNumber {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (t, o) = strip_parentheses(s, 0);
+        let mut depth = 0;
+        let mut slash = None;
+        for (i, c) in t.char_indices() {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                '/' if depth == 0 => {
+                    if slash.is_some() {
+                        return Err(ParseError::new(
+                            o + i,
+                            ParseErrorKind::UnexpectedCharacter(c),
+                        ));
+                    }
+                    slash = Some(i);
+                }
+                _ => {}
+            }
+        }
+        match slash {
+            None => parse_i64(t, o).map(|n| RationalNumber::new(n, 1)),
+            Some(i) => {
+                let n = parse_integer(&t[..i], o)?;
+                let (d_str, d_offset) = trim(&t[i + 1..], o + i + 1);
+                let d = parse_integer(d_str, d_offset)?;
+                if d == 0 {
+                    return Err(ParseError::new(d_offset, ParseErrorKind::ZeroDenominator));
+                }
+                let (n, d) = if d < 0 { (-n, -d) } else { (n, d) };
+                if n < i64::MIN as i128 || n > i64::MAX as i128 {
+                    return Err(ParseError::new(o, ParseErrorKind::InvalidNumber));
+                }
+                if d > u64::MAX as i128 {
+                    return Err(ParseError::new(d_offset, ParseErrorKind::InvalidNumber));
+                }
+                Ok(RationalNumber::new(n as i64, d as u64))
+            }
+        }
+    }
+}
+
+/// ```\\frac{1}{2}```のように書く。負のときは分数の前に負号を付け、分母が1なら整数だけを書く。
+impl ToLatex for RationalNumber {
+    fn to_latex(&self) -> String {
+        let sign = if self.numerator < 0 { "-" } else { "" };
+        if self.denominator == 1 {
+            self.numerator.to_string()
+        } else {
+            format!(
+                "{}\\frac{{{}}}{{{}}}",
+                sign,
+                self.numerator.unsigned_abs(),
+                self.denominator
+            )
+        }
+    }
+}
+
 impl Add for RationalNumber {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
@@ -145,8 +296,37 @@ impl Inverse for RationalNumber {
     }
 }
 
+/// 平方根の実装。分子と分母がともに平方数のときだけ```Some```を返す（負の数は```None```）。
+impl SquareRoot for RationalNumber {
+    fn square_root(self) -> Option<RationalNumber> {
+        if self.numerator < 0 {
+            return None;
+        }
+        let n = integer_square_root(self.numerator as u64)?;
+        let d = integer_square_root(self.denominator)?;
+        Some(RationalNumber::new(n as i64, d))
+    }
+}
+
+/// ```n```が平方数ならその平方根を返す。
+fn integer_square_root(n: u64) -> Option<u64> {
+    // f64で近似してから前後を確かめる
+    let r = (n as f64).sqrt() as u64;
+    (r.saturating_sub(1)..=r + 1).find(|&x| x.checked_mul(x) == Some(n))
+}
+
 impl Characteristic for RationalNumber {
     fn characteristic() -> u64 {
         0
     }
 }
+
+impl AdditiveGroup for RationalNumber {}
+
+impl Ring for RationalNumber {}
+
+impl CommutativeRing for RationalNumber {}
+
+impl IntegralDomain for RationalNumber {}
+
+impl Field for RationalNumber {}
@@ -1,17 +1,51 @@
+use crate::algebraic_structures::{AdditiveGroup, CommutativeRing, Field, IntegralDomain, Ring};
 use crate::characteristic::Characteristic;
 use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::latex::ToLatex;
+use crate::parse::{strip_parentheses, ParseError, ParseErrorKind};
+use crate::scalar::Scalar;
+use crate::square_root::SquareRoot;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// 加減乗除など以外にもトレイトを実装するため、```f64```型と実質的には同等な```RealNumber```構造体を新たに定義。
+///
+/// 環や体として扱うために```Eq```と```Hash```も実装する。そのため、作るときにNaNは1種類のNaNに、
+/// -0.0は0.0にそろえておき、比較はビット列で行う（NaNどうしも等しいとみなす）。
+/// 丸め誤差を許して比べたいときは```approx_eq()```を使う。
 #[derive(Debug, Clone, Copy)]
 pub struct RealNumber {
     value: f64,
 }
 
+impl PartialEq for RealNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.value.to_bits() == other.value.to_bits()
+    }
+}
+
+impl Eq for RealNumber {}
+
+impl Hash for RealNumber {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.to_bits().hash(state);
+    }
+}
+
 impl RealNumber {
+    /// NaNと-0.0をそろえてから作る。演算の結果もすべてここを通す。
     pub fn new(r: f64) -> Self {
-        RealNumber { value: r }
+        let value = if r.is_nan() {
+            f64::NAN
+        } else if r == 0f64 {
+            0f64
+        } else {
+            r
+        };
+        RealNumber { value }
     }
 
     pub fn to_f64(&self) -> f64 {
@@ -25,61 +59,72 @@ impl fmt::Display for RealNumber {
     }
 }
 
+/// ```-1.5```（```Display```の形式）や```(-1.5)```を読む。
+/// 数として読めないものと同じく、```NaN```や```inf```のような有限でない値は```InvalidNumber```にする。
+impl FromStr for RealNumber {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (t, o) = strip_parentheses(s, 0);
+        if t.is_empty() {
+            return Err(ParseError::new(o, ParseErrorKind::Empty));
+        }
+        t.parse::<f64>()
+            .ok()
+            .filter(|x| x.is_finite())
+            .map(RealNumber::new)
+            .ok_or_else(|| ParseError::new(o, ParseErrorKind::InvalidNumber))
+    }
+}
+
+impl ToLatex for RealNumber {
+    fn to_latex(&self) -> String {
+        self.value.to_string()
+    }
+}
+
 impl Add for RealNumber {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            value: self.value + rhs.value,
-        }
+        RealNumber::new(self.value + rhs.value)
     }
 }
 
 impl AddAssign for RealNumber {
     fn add_assign(&mut self, other: Self) {
-        *self = Self {
-            value: self.value + other.value,
-        };
+        *self = RealNumber::new(self.value + other.value);
     }
 }
 
 impl Sub for RealNumber {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            value: self.value - rhs.value,
-        }
+        RealNumber::new(self.value - rhs.value)
     }
 }
 
 impl SubAssign for RealNumber {
     fn sub_assign(&mut self, other: Self) {
-        *self = Self {
-            value: self.value - other.value,
-        };
+        *self = RealNumber::new(self.value - other.value);
     }
 }
 
 impl Mul for RealNumber {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
-        Self {
-            value: self.value * rhs.value,
-        }
+        RealNumber::new(self.value * rhs.value)
     }
 }
 
 impl MulAssign for RealNumber {
     fn mul_assign(&mut self, other: Self) {
-        *self = Self {
-            value: self.value * other.value,
-        };
+        *self = RealNumber::new(self.value * other.value);
     }
 }
 
 impl Neg for RealNumber {
     type Output = Self;
     fn neg(self) -> Self {
-        Self { value: -self.value }
+        RealNumber::new(-self.value)
     }
 }
 
@@ -100,3 +145,53 @@ impl Characteristic for RealNumber {
         0
     }
 }
+
+/// 逆元の実装。0のときだけ```None```を返す。
+impl Inverse for RealNumber {
+    fn inverse(self) -> Option<RealNumber> {
+        if self.value == 0f64 {
+            None
+        } else {
+            Some(RealNumber::new(1f64 / self.value))
+        }
+    }
+}
+
+/// 平方根の実装。負のときだけ```None```を返す。
+impl SquareRoot for RealNumber {
+    fn square_root(self) -> Option<RealNumber> {
+        if self.value < 0f64 {
+            None
+        } else {
+            Some(RealNumber::new(self.value.sqrt()))
+        }
+    }
+}
+
+impl Scalar for RealNumber {
+    fn from_f64(x: f64) -> Self {
+        RealNumber::new(x)
+    }
+
+    fn real_part(&self) -> f64 {
+        self.value
+    }
+
+    fn abs(&self) -> f64 {
+        self.value.abs()
+    }
+
+    fn conjugate(&self) -> Self {
+        *self
+    }
+}
+
+impl AdditiveGroup for RealNumber {}
+
+impl Ring for RealNumber {}
+
+impl CommutativeRing for RealNumber {}
+
+impl IntegralDomain for RealNumber {}
+
+impl Field for RealNumber {}
@@ -0,0 +1,10 @@
+use crate::polynomial::Polynomial;
+
+/// 多項式の根を係数の型の中で求める関数```roots()```を持っていることを要求するトレイト。
+/// 相異なる根を決まった順に並べて返し、0多項式のときは```None```を返す
+pub trait Roots
+where
+    Self: std::marker::Sized,
+{
+    fn roots(f: &Polynomial<Self>) -> Option<Vec<Self>>;
+}
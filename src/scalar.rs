@@ -0,0 +1,24 @@
+use crate::algebraic_structures::Field;
+
+/// 絶対値と複素共役をもち、浮動小数点数で数値計算のできる体（```RealNumber```と```Complex<RealNumber>```）のためのトレイト。
+/// LU分解やQR分解など、```Matrix```の数値計算に使う。
+///
+/// 丸め誤差があるので、比較には```==```ではなく```approx_eq()```を使う。
+pub trait Scalar: Field + Copy {
+    /// 実数から作る
+    fn from_f64(x: f64) -> Self;
+
+    /// 実部
+    fn real_part(&self) -> f64;
+
+    /// 絶対値
+    fn abs(&self) -> f64;
+
+    /// 複素共役（実数ならそのまま）
+    fn conjugate(&self) -> Self;
+
+    /// 差の絶対値が```tolerance```以下かどうか
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        (*self - *other).abs() <= tolerance
+    }
+}
@@ -1,24 +1,73 @@
+use crate::format::Notation;
+use crate::latex::ToLatex;
 use std::cmp::Eq;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::hash::Hash;
 
 /// 解集合
+///
+/// 重根なども扱えるよう、各元の重複度も合わせて持っておく。
 #[derive(Clone, Debug)]
 pub struct SolutionSet<T: Hash> {
-    solutions: std::collections::HashSet<T>,
+    solutions: HashMap<T, usize>,
 }
 
-impl<T: Hash> SolutionSet<T> {
-    /// コンストラクタ。面倒なので所有権が移ることにしてしまった。
-    pub fn new(s: std::collections::HashSet<T>) -> Self {
-        Self { solutions: s }
+impl<T: Hash + Eq> SolutionSet<T> {
+    /// コンストラクタ。面倒なので所有権が移ることにしてしまった。重複度はすべて1とする。
+    pub fn new(s: HashSet<T>) -> Self {
+        Self {
+            solutions: s.into_iter().map(|t| (t, 1)).collect(),
+        }
     }
-}
 
-impl<T: Hash + Eq> SolutionSet<T> {
-    /// 元を加える。
+    /// 元を加える。すでに入っている元の場合は何もしない。
     pub fn insert(&mut self, t: T) {
-        self.solutions.insert(t);
+        self.solutions.entry(t).or_insert(1);
+    }
+
+    /// 重複度を指定して元を加える。すでに入っている元の場合は重複度を足し合わせる。
+    pub fn insert_with_multiplicity(&mut self, t: T, m: usize) {
+        *self.solutions.entry(t).or_insert(0) += m;
+    }
+
+    /// 元の重複度を返す。含まれていない元に対しては0を返す。
+    pub fn multiplicity(&self, t: &T) -> usize {
+        self.solutions.get(t).copied().unwrap_or(0)
+    }
+
+    /// 元を含んでいるかどうか
+    pub fn contains(&self, t: &T) -> bool {
+        self.solutions.contains_key(t)
+    }
+
+    /// （重複度を考えない）元の個数
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    /// 空集合かどうか
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+
+    /// 元とその重複度の組を順に返すイテレータ
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.solutions.iter().map(|(t, &m)| (t, m))
+    }
+}
+
+impl<T: Hash> SolutionSet<T> {
+    /// 各元を```item```で文字列にして、集合らしく中括弧で囲んで並べる。
+    /// ```Notation::Latex```のときは ```\{ a, b \}``` のように書く。
+    pub fn to_string_with<F: Fn(&T) -> String>(&self, notation: Notation, item: F) -> String {
+        let items: Vec<String> = self.solutions.keys().map(item).collect();
+        match (notation, items.is_empty()) {
+            (Notation::Latex, true) => "\\emptyset".to_string(),
+            (Notation::Latex, false) => format!("\\{{ {} \\}}", items.join(", ")),
+            (_, true) => "{ }".to_string(),
+            (_, false) => format!("{{{}}}", items.join(", ")),
+        }
     }
 }
 
@@ -26,19 +75,17 @@ impl<T: Hash + Eq> SolutionSet<T> {
 /// 型```T```がそもそも```Display```トレイトを実装していることを要求。
 impl<T: fmt::Display + Hash> fmt::Display for SolutionSet<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut s: String = String::new();
-        if self.solutions.len() == 0 {
-            s.push_str(&"{ }");
-        } else {
-            s.push_str(&"{");
-            for solution in &self.solutions {
-                s.push_str(&solution.to_string());
-                s.push_str(&", ");
-            }
-            s.pop();
-            s.pop();
-            s.push_str(&"}");
-        }
-        write!(f, "{}", s)
+        write!(
+            f,
+            "{}",
+            self.to_string_with(Notation::Plain, |t| t.to_string())
+        )
+    }
+}
+
+/// ```\\{ a, b \\}```のように書く。空集合は```\\emptyset```。
+impl<T: ToLatex + Hash> ToLatex for SolutionSet<T> {
+    fn to_latex(&self) -> String {
+        self.to_string_with(Notation::Latex, T::to_latex)
     }
 }